@@ -0,0 +1,78 @@
+use criterion::{criterion_group, criterion_main, Criterion, black_box};
+
+use specs::{World, WorldExt, Builder, Component, VecStorage, ReadStorage, System, RunNow, Join};
+
+use std::sync::{Arc, RwLock};
+
+#[path = "../src/ecs_access.rs"]
+mod ecs_access;
+
+use ecs_access::WorldAccess;
+
+const ENTITY_COUNT: u32 = 1000;
+const SYSTEMS_PER_FRAME: usize = 4;
+
+#[derive(Debug)]
+struct Counter(u32);
+
+impl Component for Counter {
+    type Storage = VecStorage<Self>;
+}
+
+struct SumCounters;
+
+impl<'a> System<'a> for SumCounters {
+    type SystemData = ReadStorage<'a, Counter>;
+
+    fn run(&mut self, counters: Self::SystemData) {
+        black_box((&counters).join().map(|counter| counter.0 as u64).sum::<u64>());
+    }
+}
+
+fn build_world() -> Arc<RwLock<World>> {
+    let mut world = World::new();
+    world.register::<Counter>();
+
+    for i in 0..ENTITY_COUNT {
+        world.create_entity().with(Counter(i)).build();
+    }
+
+    Arc::new(RwLock::new(world))
+}
+
+// The old scene pattern: every system call takes the lock and maps the poison error itself.
+fn lock_per_system(ecs: &Arc<RwLock<World>>) -> Result<(), String> {
+    for _ in 0..SYSTEMS_PER_FRAME {
+        let world = ecs.read().map_err(|e| e.to_string())?;
+        SumCounters.run_now(&*world);
+    }
+
+    Ok(())
+}
+
+// The scoped accessor: one lock per scene call, systems run on a plain &World.
+fn lock_per_frame(ecs: &Arc<RwLock<World>>) {
+    ecs.with_world(|world| {
+        for _ in 0..SYSTEMS_PER_FRAME {
+            SumCounters.run_now(world);
+        }
+    });
+}
+
+fn world_access(c: &mut Criterion) {
+    let ecs = build_world();
+
+    c.bench_function("lock per system", |b| b.iter(|| lock_per_system(black_box(&ecs))));
+    c.bench_function("lock per frame", |b| b.iter(|| lock_per_frame(black_box(&ecs))));
+    c.bench_function("plain world", |b| {
+        let world = ecs.read_world();
+        b.iter(|| {
+            for _ in 0..SYSTEMS_PER_FRAME {
+                SumCounters.run_now(black_box(&*world));
+            }
+        })
+    });
+}
+
+criterion_group!(benches, world_access);
+criterion_main!(benches);
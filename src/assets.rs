@@ -0,0 +1,474 @@
+use coffee::graphics::{Font, Gpu, Image, Target};
+
+use serde::Deserialize;
+
+use anyhow::Result;
+use thiserror::Error;
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::fs::read;
+use std::mem::ManuallyDrop;
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use tracing::{debug, info};
+
+use game_engine::load::JSONLoad;
+use game_engine::globals::font_dict::FONT_DICT_LOAD_ID;
+use game_engine::globals::image_dict::IMAGE_DICT_LOAD_ID;
+use game_engine::globals::audio_controller::AUDIO_CONTROLLER_LOAD_ID;
+
+use crate::globals::{load_json, convert_json, JSONSource, LoadErrors, LOAD_PATH, JSON_ASSETS_DIR, JSON_FILE};
+use crate::globals::TestGlobalError::FileReadError;
+use crate::atlas::{AtlasManifest, AtlasRegion};
+use crate::assets::AssetError::UnknownAssetError;
+
+// What a scene needs loaded while it's on the stack, by the names used in the dict files.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SceneAssetsJSON {
+    #[serde(default)]
+    pub fonts: Vec<String>,
+    #[serde(default)]
+    pub images: Vec<String>,
+    #[serde(default)]
+    pub sounds: Vec<String>,
+    // Names of manifests written by the pack-atlas command.
+    #[serde(default)]
+    pub atlases: Vec<String>
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct FontDictJSON {
+    fonts: HashMap<String, String>
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct ImageDictJSON {
+    images: HashMap<String, String>
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct AudioDictJSON {
+    sounds: HashMap<String, String>
+}
+
+// Name to file path for every asset the game knows about. Reading it loads nothing.
+#[derive(Debug, Default)]
+pub struct AssetCatalog {
+    fonts: HashMap<String, String>,
+    images: HashMap<String, String>,
+    sounds: HashMap<String, String>
+}
+
+fn dict_path(load_id: &str) -> String {
+    [LOAD_PATH, JSON_ASSETS_DIR, load_id, JSON_FILE].join("")
+}
+
+fn read_dict<T: for<'de> Deserialize<'de>>(file_path: &str) -> Result<T> {
+    let json: JSONLoad = load_json(file_path)?;
+
+    convert_json(&json.actual_value, std::any::type_name::<T>())
+        .map_err(|e| JSONSource::new(file_path, "/actual_value").locate(anyhow::Error::new(e)))
+}
+
+impl AssetCatalog {
+    pub fn load() -> Result<Self> {
+        let mut errors = LoadErrors::default();
+
+        let fonts = errors.collect(read_dict::<FontDictJSON>(dict_path(FONT_DICT_LOAD_ID).as_str()));
+        let images = errors.collect(read_dict::<ImageDictJSON>(dict_path(IMAGE_DICT_LOAD_ID).as_str()));
+        let sounds = errors.collect(read_dict::<AudioDictJSON>(dict_path(AUDIO_CONTROLLER_LOAD_ID).as_str()));
+
+        errors.into_result(AssetCatalog {
+            fonts: fonts.unwrap_or_default().fonts,
+            images: images.unwrap_or_default().images,
+            sounds: sounds.unwrap_or_default().sounds
+        })
+    }
+
+    pub fn images(&self) -> &HashMap<String, String> {
+        &self.images
+    }
+
+    fn path<'a>(paths: &'a HashMap<String, String>, kind: &str, name: &str, source: &JSONSource) -> Result<&'a str> {
+        paths.get(name)
+            .map(String::as_str)
+            .ok_or_else(|| source.locate(anyhow::Error::new(UnknownAssetError {
+                kind: kind.to_string(),
+                name: name.to_string()
+            })))
+    }
+}
+
+struct Cached<T> {
+    asset: T,
+    references: usize
+}
+
+// coffee fonts borrow their bytes for 'static, so the bytes are leaked while the font is loaded
+// and taken back once it has been dropped.
+pub struct LoadedFont {
+    font: ManuallyDrop<Font>,
+    bytes: &'static [u8]
+}
+
+impl LoadedFont {
+    fn new(gpu: &mut Gpu, bytes: Vec<u8>) -> coffee::Result<Self> {
+        let bytes: &'static [u8] = Box::leak(bytes.into_boxed_slice());
+
+        match Font::from_bytes(gpu, bytes) {
+            Ok(font) => Ok(LoadedFont {
+                font: ManuallyDrop::new(font),
+                bytes
+            }),
+            Err(e) => {
+                // Nothing holds the bytes if the font failed to build.
+                unsafe { drop(Box::from_raw(bytes as *const [u8] as *mut [u8])) };
+                Err(e)
+            }
+        }
+    }
+}
+
+impl Drop for LoadedFont {
+    fn drop(&mut self) {
+        // The font is the only borrower of the bytes, so they're free to reclaim once it's gone.
+        unsafe {
+            ManuallyDrop::drop(&mut self.font);
+            drop(Box::from_raw(self.bytes as *const [u8] as *mut [u8]));
+        }
+    }
+}
+
+pub struct LoadedAtlas {
+    manifest: AtlasManifest,
+    pages: Vec<Image>
+}
+
+#[derive(Default)]
+pub struct LoadedAssets {
+    fonts: HashMap<String, Cached<LoadedFont>>,
+    images: HashMap<String, Cached<Image>>,
+    // Paths only; the AudioPlayer decodes a sound on first play and frees it once it's gone from here.
+    sounds: HashMap<String, Cached<String>>,
+    atlases: HashMap<String, Cached<LoadedAtlas>>
+}
+
+impl LoadedAssets {
+    pub fn font_mut(&mut self, name: &str) -> Option<&mut Font> {
+        self.fonts.get_mut(name).map(|cached| &mut *cached.asset.font)
+    }
+
+    pub fn image(&self, name: &str) -> Option<&Image> {
+        self.images.get(name).map(|cached| &cached.asset)
+    }
+
+    pub fn sound_path(&self, name: &str) -> Option<&str> {
+        self.sounds.get(name).map(|cached| cached.asset.as_str())
+    }
+
+    // Looks a region up in every loaded atlas; the key identifies its page for batching.
+    pub fn region(&self, name: &str) -> Option<((&str, usize), AtlasRegion)> {
+        self.atlases.iter()
+            .find_map(|(atlas_name, cached)| {
+                cached.asset.manifest.regions.get(name)
+                    .map(|region| ((atlas_name.as_str(), region.page), *region))
+            })
+    }
+
+    pub fn atlas_page(&self, key: (&str, usize)) -> Option<&Image> {
+        self.atlases.get(key.0).and_then(|cached| cached.asset.pages.get(key.1))
+    }
+
+    // Flushes text queued with Font::add through `target`'s transformation.
+    pub fn draw_fonts(&mut self, target: &mut Target) {
+        for cached in self.fonts.values_mut() {
+            cached.asset.font.draw(target);
+        }
+    }
+}
+
+// Shared by every scene on the stack. Assets load when the first scene that declares them is
+// pushed and are dropped when the last one is popped.
+#[derive(Clone, Default)]
+pub struct AssetCache {
+    loaded: Arc<Mutex<LoadedAssets>>
+}
+
+impl Debug for AssetCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let loaded = self.lock();
+        f.debug_struct("AssetCache")
+            .field("fonts", &loaded.fonts.keys().collect::<Vec<_>>())
+            .field("images", &loaded.images.keys().collect::<Vec<_>>())
+            .field("sounds", &loaded.sounds.keys().collect::<Vec<_>>())
+            .field("atlases", &loaded.atlases.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl AssetCache {
+    fn lock(&self) -> std::sync::MutexGuard<'_, LoadedAssets> {
+        self.loaded.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // Resolves and reads files up front so a missing asset is reported with the scene's other load
+    // errors; only GPU uploads of assets that aren't already cached are left to the AssetUpload.
+    pub fn acquire(&self, assets: &SceneAssetsJSON, catalog: &AssetCatalog, source: &JSONSource) -> Result<AssetUpload> {
+        let mut errors = LoadErrors::default();
+        let mut fonts: Vec<(String, Vec<u8>)> = Vec::new();
+        let mut images: Vec<(String, String)> = Vec::new();
+        let mut sounds: Vec<(String, String)> = Vec::new();
+
+        {
+            let loaded = self.lock();
+
+            for (index, name) in assets.fonts.iter().enumerate() {
+                let source = source.join(format!("/fonts/{}", index).as_str());
+                let path = match errors.collect(AssetCatalog::path(&catalog.fonts, "font", name, &source)) {
+                    Some(path) => path,
+                    None => continue
+                };
+
+                if loaded.fonts.contains_key(name) {
+                    continue;
+                }
+
+                if let Some(bytes) = errors.collect(read_asset(path, &source)) {
+                    fonts.push((name.clone(), bytes));
+                }
+            }
+        }
+
+        for (index, name) in assets.images.iter().enumerate() {
+            let source = source.join(format!("/images/{}", index).as_str());
+            if let Some(path) = errors.collect(AssetCatalog::path(&catalog.images, "image", name, &source)) {
+                if errors.collect(check_exists(path, &source)).is_some() {
+                    images.push((name.clone(), path.to_string()));
+                }
+            }
+        }
+
+        for (index, name) in assets.sounds.iter().enumerate() {
+            let source = source.join(format!("/sounds/{}", index).as_str());
+            if let Some(path) = errors.collect(AssetCatalog::path(&catalog.sounds, "sound", name, &source)) {
+                if errors.collect(check_exists(path, &source)).is_some() {
+                    sounds.push((name.clone(), path.to_string()));
+                }
+            }
+        }
+
+        let mut atlases: Vec<(String, AtlasManifest)> = Vec::new();
+        for (index, name) in assets.atlases.iter().enumerate() {
+            let source = source.join(format!("/atlases/{}", index).as_str());
+            if let Some(manifest) = errors.collect(AtlasManifest::load(name).map_err(|e| source.locate(e))) {
+                let missing_page = manifest.pages.iter()
+                    .map(|page| check_exists(page, &source))
+                    .find_map(|result| result.err());
+
+                match missing_page {
+                    Some(e) => errors.push(e),
+                    None => atlases.push((name.clone(), manifest))
+                }
+            }
+        }
+
+        errors.into_result(AssetUpload {
+            cache: self.clone(),
+            declared: assets.clone(),
+            fonts,
+            images,
+            sounds,
+            atlases
+        })
+    }
+
+    pub fn with_loaded<R, F: FnOnce(&mut LoadedAssets) -> R>(&self, f: F) -> R {
+        f(&mut *self.lock())
+    }
+}
+
+// A scene's assets, read and checked by acquire, waiting on the GPU. The scene's load Task runs the
+// upload as a stage of its own.
+pub struct AssetUpload {
+    cache: AssetCache,
+    declared: SceneAssetsJSON,
+    fonts: Vec<(String, Vec<u8>)>,
+    images: Vec<(String, String)>,
+    sounds: Vec<(String, String)>,
+    atlases: Vec<(String, AtlasManifest)>
+}
+
+impl AssetUpload {
+    pub fn upload(self, gpu: &mut Gpu) -> coffee::Result<SceneAssets> {
+        let AssetUpload { cache, declared, fonts, images, sounds, atlases } = self;
+        let mut loaded = cache.lock();
+
+        // Nothing holds the assets inserted before a failed upload, so they're released again
+        // rather than left cached with no references.
+        let mut inserted = SceneAssetsJSON::default();
+        if let Err(e) = upload_missing(gpu, &mut loaded, &mut inserted, fonts, &images, &atlases) {
+            release(&mut loaded.fonts, &inserted.fonts, "font");
+            release(&mut loaded.images, &inserted.images, "image");
+            release(&mut loaded.atlases, &inserted.atlases, "atlas");
+            return Err(e);
+        }
+
+        for (name, path) in &sounds {
+            loaded.sounds.entry(name.clone())
+                .or_insert_with(|| Cached { asset: path.clone(), references: 0 });
+        }
+
+        retain(&mut loaded, &declared);
+        drop(loaded);
+
+        Ok(SceneAssets {
+            cache,
+            declared
+        })
+    }
+}
+
+// Uploads whatever isn't cached yet, noting each name in `inserted` as it goes.
+fn upload_missing(
+    gpu: &mut Gpu,
+    loaded: &mut LoadedAssets,
+    inserted: &mut SceneAssetsJSON,
+    fonts: Vec<(String, Vec<u8>)>,
+    images: &[(String, String)],
+    atlases: &[(String, AtlasManifest)]
+) -> coffee::Result<()> {
+    for (name, bytes) in fonts {
+        if !loaded.fonts.contains_key(&name) {
+            let font = LoadedFont::new(gpu, bytes)?;
+            info!(font = %name, "Loaded font");
+            loaded.fonts.insert(name.clone(), Cached { asset: font, references: 0 });
+            inserted.fonts.push(name);
+        }
+    }
+
+    for (name, path) in images {
+        if !loaded.images.contains_key(name) {
+            let image = Image::new(gpu, path.as_str())?;
+            loaded.images.insert(name.clone(), Cached { asset: image, references: 0 });
+            inserted.images.push(name.clone());
+            info!(image = %name, "Loaded image");
+        }
+    }
+
+    for (name, manifest) in atlases {
+        if !loaded.atlases.contains_key(name) {
+            let pages = manifest.pages.iter()
+                .map(|page| Image::new(gpu, page.as_str()))
+                .collect::<coffee::Result<Vec<Image>>>()?;
+            loaded.atlases.insert(name.clone(), Cached {
+                asset: LoadedAtlas { manifest: manifest.clone(), pages },
+                references: 0
+            });
+            inserted.atlases.push(name.clone());
+            info!(atlas = %name, "Loaded atlas");
+        }
+    }
+
+    Ok(())
+}
+
+fn read_asset(path: &str, source: &JSONSource) -> Result<Vec<u8>> {
+    read(path).map_err(|e| {
+        source.locate(anyhow::Error::new(FileReadError {
+            file_path: path.to_string(),
+            source: e
+        }))
+    })
+}
+
+fn check_exists(path: &str, source: &JSONSource) -> Result<()> {
+    if Path::new(path).is_file() {
+        Ok(())
+    } else {
+        Err(source.locate(anyhow::Error::new(FileReadError {
+            file_path: path.to_string(),
+            source: std::io::Error::new(std::io::ErrorKind::NotFound, "asset file not found")
+        })))
+    }
+}
+
+fn retain(loaded: &mut LoadedAssets, declared: &SceneAssetsJSON) {
+    for name in &declared.fonts {
+        if let Some(cached) = loaded.fonts.get_mut(name) {
+            cached.references += 1;
+        }
+    }
+    for name in &declared.images {
+        if let Some(cached) = loaded.images.get_mut(name) {
+            cached.references += 1;
+        }
+    }
+    for name in &declared.sounds {
+        if let Some(cached) = loaded.sounds.get_mut(name) {
+            cached.references += 1;
+        }
+    }
+    for name in &declared.atlases {
+        if let Some(cached) = loaded.atlases.get_mut(name) {
+            cached.references += 1;
+        }
+    }
+}
+
+fn release<T>(assets: &mut HashMap<String, Cached<T>>, names: &[String], kind: &str) {
+    for name in names {
+        let unused = match assets.get_mut(name) {
+            Some(cached) => {
+                cached.references = cached.references.saturating_sub(1);
+                cached.references == 0
+            },
+            None => false
+        };
+
+        if unused {
+            assets.remove(name);
+            debug!(kind, name = %name, "Unloaded asset");
+        }
+    }
+}
+
+// A scene's hold on its declared assets; dropping the scene (popping it) releases them.
+pub struct SceneAssets {
+    cache: AssetCache,
+    declared: SceneAssetsJSON
+}
+
+impl SceneAssets {
+    pub fn with_loaded<R, F: FnOnce(&mut LoadedAssets) -> R>(&self, f: F) -> R {
+        self.cache.with_loaded(f)
+    }
+}
+
+impl Debug for SceneAssets {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SceneAssets")
+            .field("declared", &self.declared)
+            .finish()
+    }
+}
+
+impl Drop for SceneAssets {
+    fn drop(&mut self) {
+        let mut loaded = self.cache.lock();
+        release(&mut loaded.fonts, &self.declared.fonts, "font");
+        release(&mut loaded.images, &self.declared.images, "image");
+        release(&mut loaded.sounds, &self.declared.sounds, "sound");
+        release(&mut loaded.atlases, &self.declared.atlases, "atlas");
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum AssetError {
+    #[error("No {kind} named {name:?} in the asset dicts")]
+    UnknownAssetError {
+        kind: String,
+        name: String
+    }
+}
@@ -0,0 +1,103 @@
+pub mod packer;
+
+use coffee::graphics::Rectangle;
+
+use serde::{Deserialize, Serialize};
+
+use anyhow::Result;
+use thiserror::Error;
+
+use std::collections::BTreeMap;
+
+use game_engine::load::JSONLoad;
+
+use crate::globals::{load_json, convert_json, JSONSource, LOAD_PATH, JSON_ASSETS_DIR, JSON_FILE};
+use crate::globals::TestGlobalError::LoadIDMatchError;
+
+pub const TEXTURE_ATLAS_LOAD_ID: &str = "texture_atlas";
+pub const ATLASES_DIR: &str = "atlases/";
+pub const IMAGES_DIR: &str = "images/";
+
+// A named sub-rectangle of one atlas page, in pixels.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct AtlasRegion {
+    pub page: usize,
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16
+}
+
+impl AtlasRegion {
+    // Frames of `frame_size` read left to right, then top to bottom, within the region.
+    pub fn frame(&self, index: u16, frame_size: [u16; 2]) -> Rectangle<u16> {
+        let [width, height] = frame_size;
+        let columns = (self.width / width.max(1)).max(1);
+
+        Rectangle {
+            x: self.x + (index % columns) * width,
+            y: self.y + (index / columns) * height,
+            width,
+            height
+        }
+    }
+
+    pub fn source(&self) -> Rectangle<u16> {
+        Rectangle {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height
+        }
+    }
+}
+
+// Written by the pack-atlas command; pages are image paths, regions are keyed by image_dict name.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct AtlasManifest {
+    pub pages: Vec<String>,
+    pub regions: BTreeMap<String, AtlasRegion>
+}
+
+impl AtlasManifest {
+    pub fn file_path(name: &str) -> String {
+        [LOAD_PATH, JSON_ASSETS_DIR, ATLASES_DIR, name, JSON_FILE].join("")
+    }
+
+    pub fn page_path(name: &str, page: usize) -> String {
+        format!("{}{}{}{}_{}.png", LOAD_PATH, IMAGES_DIR, ATLASES_DIR, name, page)
+    }
+
+    pub fn load(name: &str) -> Result<Self> {
+        let file_path = Self::file_path(name);
+        let json: JSONLoad = load_json(file_path.as_str())?;
+
+        if json.load_type_id != TEXTURE_ATLAS_LOAD_ID {
+            return Err(JSONSource::new(file_path.as_str(), "/load_type_id").locate(anyhow::Error::new(
+                LoadIDMatchError {
+                    expected_id: TEXTURE_ATLAS_LOAD_ID.to_string(),
+                    actual_id: json.load_type_id
+                }
+            )))
+        }
+
+        convert_json(&json.actual_value, stringify!(AtlasManifest))
+            .map_err(|e| JSONSource::new(file_path.as_str(), "/actual_value").locate(anyhow::Error::new(e)))
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum PackError {
+    #[error("Image {name} is {width}x{height}, larger than the {max_size}px atlas page")]
+    ImageTooLargeError {
+        name: String,
+        width: u32,
+        height: u32,
+        max_size: u32
+    },
+    #[error("Max size {max_size}px is larger than the {limit}px an atlas region can address")]
+    MaxSizeError {
+        max_size: u32,
+        limit: u32
+    }
+}
@@ -0,0 +1,140 @@
+use image::{RgbaImage, GenericImage, imageops};
+
+use serde_json::json;
+
+use anyhow::Result;
+
+use std::fs::{create_dir_all, write};
+use std::path::Path;
+
+use tracing::info;
+
+use crate::assets::AssetCatalog;
+use crate::atlas::{AtlasManifest, AtlasRegion, TEXTURE_ATLAS_LOAD_ID};
+use crate::atlas::PackError::ImageTooLargeError;
+
+struct Page {
+    image: RgbaImage,
+    cursor_x: u32,
+    cursor_y: u32,
+    shelf_height: u32,
+    used_width: u32
+}
+
+impl Page {
+    fn new(max_size: u32) -> Self {
+        Page {
+            image: RgbaImage::new(max_size, max_size),
+            cursor_x: 0,
+            cursor_y: 0,
+            shelf_height: 0,
+            used_width: 0
+        }
+    }
+
+    // Shelf packing: fill rows left to right, starting a new row below the tallest image so far.
+    fn place(&mut self, width: u32, height: u32, padding: u32, max_size: u32) -> Option<(u32, u32)> {
+        if self.cursor_x + width > max_size {
+            self.cursor_x = 0;
+            self.cursor_y += self.shelf_height + padding;
+            self.shelf_height = 0;
+        }
+
+        if self.cursor_y + height > max_size {
+            return None
+        }
+
+        let position = (self.cursor_x, self.cursor_y);
+        self.cursor_x += width + padding;
+        self.shelf_height = self.shelf_height.max(height);
+        self.used_width = self.used_width.max(position.0 + width);
+
+        Some(position)
+    }
+
+    fn into_trimmed(self) -> RgbaImage {
+        let used_height = self.cursor_y + self.shelf_height;
+        imageops::crop_imm(&self.image, 0, 0, self.used_width, used_height).to_image()
+    }
+}
+
+// Packs every image in image_dict.json into as few pages of at most `max_size` pixels as it can,
+// then writes the pages and a manifest that AtlasSprite and Animation regions are looked up in.
+pub fn pack_atlas(name: &str, max_size: u32, padding: u32) -> Result<AtlasManifest> {
+    let catalog = AssetCatalog::load()?;
+
+    let mut images: Vec<(String, RgbaImage)> = Vec::new();
+    for (image_name, path) in catalog.images() {
+        let image = image::open(path)?.to_rgba8();
+        if image.width() > max_size || image.height() > max_size {
+            return Err(anyhow::Error::new(ImageTooLargeError {
+                name: image_name.clone(),
+                width: image.width(),
+                height: image.height(),
+                max_size
+            }))
+        }
+
+        images.push((image_name.clone(), image));
+    }
+
+    // Tallest first keeps shelves tight; the name tie-break keeps the output stable between runs.
+    images.sort_by(|(a_name, a), (b_name, b)| b.height().cmp(&a.height()).then(a_name.cmp(b_name)));
+
+    let mut pages: Vec<Page> = Vec::new();
+    let mut manifest = AtlasManifest::default();
+
+    for (image_name, image) in &images {
+        let placed = pages.iter_mut().enumerate()
+            .find_map(|(index, page)| {
+                page.place(image.width(), image.height(), padding, max_size).map(|(x, y)| (index, x, y))
+            });
+
+        let (page_index, x, y) = match placed {
+            Some(placed) => placed,
+            None => {
+                let mut page = Page::new(max_size);
+                let (x, y) = page.place(image.width(), image.height(), padding, max_size)
+                    .expect("an empty page fits any image within max_size");
+                pages.push(page);
+                (pages.len() - 1, x, y)
+            }
+        };
+
+        pages[page_index].image.copy_from(image, x, y)?;
+        manifest.regions.insert(image_name.clone(), AtlasRegion {
+            page: page_index,
+            x: x as u16,
+            y: y as u16,
+            width: image.width() as u16,
+            height: image.height() as u16
+        });
+    }
+
+    for (index, page) in pages.into_iter().enumerate() {
+        let page_path = AtlasManifest::page_path(name, index);
+        if let Some(directory) = Path::new(page_path.as_str()).parent() {
+            create_dir_all(directory)?;
+        }
+
+        page.into_trimmed().save(page_path.as_str())?;
+        manifest.pages.push(page_path);
+    }
+
+    let manifest_path = AtlasManifest::file_path(name);
+    if let Some(directory) = Path::new(manifest_path.as_str()).parent() {
+        create_dir_all(directory)?;
+    }
+
+    write(
+        manifest_path.as_str(),
+        serde_json::to_string_pretty(&json!({
+            "load_type_id": TEXTURE_ATLAS_LOAD_ID,
+            "actual_value": manifest
+        }))?
+    )?;
+
+    info!(atlas = name, pages = manifest.pages.len(), regions = manifest.regions.len(), "Packed atlas");
+
+    Ok(manifest)
+}
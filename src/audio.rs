@@ -0,0 +1,146 @@
+use kira::manager::{AudioManager, AudioManagerSettings};
+use kira::sound::SoundSettings;
+use kira::sound::handle::SoundHandle;
+use kira::instance::{InstanceSettings, InstanceState};
+use kira::instance::handle::InstanceHandle;
+
+use serde::{Deserialize, Serialize};
+
+use anyhow::Result;
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fmt::{Debug, Formatter};
+
+use tracing::{info, debug, warn};
+
+use crate::settings::AudioSettings;
+
+// Which of the volume settings a sound follows, on top of the master volume.
+#[derive(Deserialize, Serialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Channel {
+    Music,
+    Sfx
+}
+
+impl Default for Channel {
+    fn default() -> Self {
+        Channel::Sfx
+    }
+}
+
+// Plays sounds through kira at the volumes in AudioSettings, including ones already playing when
+// the settings change. Sounds are decoded on first play. Without an output device every call is a no-op.
+pub struct AudioPlayer {
+    manager: Option<AudioManager>,
+    sounds: HashMap<String, SoundHandle>,
+    instances: Vec<(Channel, InstanceHandle)>,
+    settings: AudioSettings
+}
+
+impl Default for AudioPlayer {
+    fn default() -> Self {
+        AudioPlayer::silent(AudioSettings::default())
+    }
+}
+
+impl Debug for AudioPlayer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AudioPlayer")
+            .field("output", &self.manager.is_some())
+            .field("sounds", &self.sounds.keys().collect::<Vec<_>>())
+            .field("playing", &self.instances.len())
+            .field("settings", &self.settings)
+            .finish()
+    }
+}
+
+impl AudioPlayer {
+    pub fn new(settings: AudioSettings) -> Self {
+        let manager = match AudioManager::new(AudioManagerSettings::default()) {
+            Ok(manager) => Some(manager),
+            Err(e) => {
+                warn!("No audio output, sounds won't play: {}", e);
+                None
+            }
+        };
+
+        AudioPlayer {
+            manager,
+            ..AudioPlayer::silent(settings)
+        }
+    }
+
+    // For headless runs, which shouldn't need or grab an audio device.
+    pub fn silent(settings: AudioSettings) -> Self {
+        AudioPlayer {
+            manager: None,
+            sounds: HashMap::new(),
+            instances: Vec::new(),
+            settings
+        }
+    }
+
+    pub fn set_settings(&mut self, settings: AudioSettings) {
+        if settings == self.settings {
+            return;
+        }
+        self.settings = settings;
+
+        self.instances.retain(|(_, instance)| !matches!(instance.state(), InstanceState::Stopped));
+        for (channel, instance) in &mut self.instances {
+            if let Err(e) = instance.set_volume(volume(&settings, *channel)) {
+                warn!("Failed to change the volume of a playing sound: {}", e);
+            }
+        }
+    }
+
+    pub fn play(&mut self, name: &str, path: &str, channel: Channel) -> Result<()> {
+        let manager = match self.manager.as_mut() {
+            Some(manager) => manager,
+            None => return Ok(())
+        };
+
+        let sound = match self.sounds.entry(name.to_string()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                info!(sound = %name, "Decoded sound");
+                entry.insert(manager.load_sound(path, SoundSettings::default())?)
+            }
+        };
+
+        let instance = sound.play(InstanceSettings::new().volume(volume(&self.settings, channel)))?;
+        // Finished sounds are dropped here, so the list only ever holds what could still be playing.
+        self.instances.retain(|(_, instance)| !matches!(instance.state(), InstanceState::Stopped));
+        self.instances.push((channel, instance));
+
+        Ok(())
+    }
+
+    // Frees decoded sounds that `keep` no longer wants, e.g. once no scene on the stack declares them.
+    pub fn retain_sounds<F: Fn(&str) -> bool>(&mut self, keep: F) {
+        let unused: Vec<String> = self.sounds.keys()
+            .filter(|name| !keep(name.as_str()))
+            .cloned()
+            .collect();
+
+        for name in unused {
+            if let Some(sound) = self.sounds.remove(&name) {
+                if let Some(manager) = self.manager.as_mut() {
+                    if let Err(e) = manager.remove_sound(sound.id()) {
+                        warn!(sound = %name, "Failed to free sound: {}", e);
+                    }
+                }
+                debug!(sound = %name, "Freed sound");
+            }
+        }
+    }
+}
+
+fn volume(settings: &AudioSettings, channel: Channel) -> f64 {
+    match channel {
+        Channel::Music => settings.music(),
+        Channel::Sfx => settings.sfx()
+    }
+}
@@ -0,0 +1,154 @@
+use structopt::StructOpt;
+
+use once_cell::sync::OnceCell;
+
+use anyhow::{Result, Error};
+
+use std::env::{set_current_dir, current_dir};
+use std::path::PathBuf;
+
+use serde_json::json;
+
+use tracing::{info, warn};
+
+use crate::settings::SETTINGS_FILE;
+use crate::globals::{write_temp_json, TempJSON};
+use crate::atlas::PackError::MaxSizeError;
+
+// Anything given here overrides settings.json for this run only.
+#[derive(StructOpt, Debug)]
+#[structopt(name = "test_game")]
+pub struct Cli {
+    /// Settings file to read, and to save options menu changes to
+    #[structopt(long, default_value = SETTINGS_FILE)]
+    pub settings: String,
+
+    /// Window title
+    #[structopt(long)]
+    pub title: Option<String>,
+
+    /// Window width in pixels
+    #[structopt(long)]
+    pub width: Option<u32>,
+
+    /// Window height in pixels
+    #[structopt(long)]
+    pub height: Option<u32>,
+
+    /// Start fullscreen
+    #[structopt(long)]
+    pub fullscreen: bool,
+
+    /// Start in a window, even if settings say fullscreen
+    #[structopt(long, conflicts_with = "fullscreen")]
+    pub windowed: bool,
+
+    /// Scene stack to start with, by file name under assets/JSON/scene_stacks
+    #[structopt(long)]
+    pub scene_stack: Option<String>,
+
+    /// Master volume from 0 to 1
+    #[structopt(long)]
+    pub master_volume: Option<f64>,
+
+    /// Music volume from 0 to 1
+    #[structopt(long)]
+    pub music_volume: Option<f64>,
+
+    /// Sound effect volume from 0 to 1
+    #[structopt(long)]
+    pub sfx_volume: Option<f64>,
+
+    /// Start with a single scene file instead of a scene stack
+    #[structopt(long, conflicts_with = "scene-stack")]
+    pub scene: Option<String>,
+
+    /// Directory containing the assets folder
+    #[structopt(long, parse(from_os_str))]
+    pub asset_root: Option<PathBuf>,
+
+    /// Seed for the game's random number generator; random if not given
+    #[structopt(long)]
+    pub seed: Option<u64>,
+
+    /// Run this many fixed ticks of the starting scene without opening a window, then print an entity dump and quit
+    #[structopt(long)]
+    pub headless: Option<u64>,
+
+    /// Show debug overlays such as the entity inspector from the start
+    #[structopt(long)]
+    pub debug_overlays: bool,
+
+    #[structopt(subcommand)]
+    pub command: Option<Command>
+}
+
+// Offline tools that run instead of the game.
+#[derive(StructOpt, Debug)]
+pub enum Command {
+    /// Pack every image in image_dict.json into atlas pages plus a JSON manifest of regions
+    PackAtlas {
+        /// Atlas name; writes assets/JSON/atlases/<name>.json and assets/images/atlases/<name>_<page>.png
+        #[structopt(long, default_value = "sprites")]
+        name: String,
+
+        /// Largest page width and height in pixels, at most 65535
+        #[structopt(long, default_value = "2048", parse(try_from_str = parse_max_size))]
+        max_size: u32,
+
+        /// Transparent pixels left between packed images
+        #[structopt(long, default_value = "2")]
+        padding: u32
+    }
+}
+
+// Atlas manifests store region coordinates as u16, so a bigger page couldn't be addressed.
+fn parse_max_size(value: &str) -> Result<u32> {
+    let max_size: u32 = value.parse()?;
+    if max_size > u16::MAX as u32 {
+        return Err(Error::new(MaxSizeError { max_size, limit: u16::MAX as u32 }))
+    }
+
+    Ok(max_size)
+}
+
+static CLI: OnceCell<Cli> = OnceCell::new();
+
+// Parsed on first use, so everything after main sees the same arguments.
+pub fn options() -> &'static Cli {
+    CLI.get_or_init(Cli::from_args)
+}
+
+impl Cli {
+    // Every asset path, including the ones inside JSON files, is relative to the directory holding
+    // assets/, so switching roots means switching the working directory. The settings path is
+    // resolved first so it still means what it did on the command line.
+    pub fn enter_asset_root(&self) -> std::io::Result<String> {
+        let settings_path = match current_dir() {
+            Ok(dir) => dir.join(self.settings.as_str()).to_string_lossy().to_string(),
+            Err(e) => {
+                if self.asset_root.is_some() {
+                    warn!(settings = %self.settings, "Couldn't resolve the settings path, so it will be read from the asset root: {}", e);
+                }
+                self.settings.clone()
+            }
+        };
+
+        if let Some(asset_root) = &self.asset_root {
+            set_current_dir(asset_root)?;
+            info!(asset_root = %asset_root.display(), "Changed asset root");
+        }
+
+        Ok(settings_path)
+    }
+}
+
+// Wraps a single scene file in a throwaway scene stack so it loads like any other.
+pub fn single_scene_stack(scene_path: &str) -> Result<TempJSON> {
+    write_temp_json("single_scene_stack", &json!({
+        "load_type_id": "scene_stack",
+        "actual_value": {
+            "scene_paths": [scene_path]
+        }
+    }))
+}
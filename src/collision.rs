@@ -0,0 +1,330 @@
+use std::collections::{BTreeSet, HashMap};
+
+// A collider placed in the world. Both shapes are measured from their centre.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Bounds {
+    Aabb {
+        center: [f32; 2],
+        half_extents: [f32; 2]
+    },
+    Circle {
+        center: [f32; 2],
+        radius: f32
+    }
+}
+
+impl Bounds {
+    pub fn center(&self) -> [f32; 2] {
+        match self {
+            Bounds::Aabb { center, .. } => *center,
+            Bounds::Circle { center, .. } => *center
+        }
+    }
+
+    // Smallest and largest corner of the box around the shape.
+    pub fn extents(&self) -> ([f32; 2], [f32; 2]) {
+        let (center, half) = match self {
+            Bounds::Aabb { center, half_extents } => (*center, *half_extents),
+            Bounds::Circle { center, radius } => (*center, [*radius, *radius])
+        };
+
+        ([center[0] - half[0], center[1] - half[1]], [center[0] + half[0], center[1] + half[1]])
+    }
+
+    pub fn translate(&mut self, by: [f32; 2]) {
+        let center = match self {
+            Bounds::Aabb { center, .. } => center,
+            Bounds::Circle { center, .. } => center
+        };
+
+        center[0] += by[0];
+        center[1] += by[1];
+    }
+}
+
+// Moving the first shape `depth` along `normal` separates it from the second.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Contact {
+    pub normal: [f32; 2],
+    pub depth: f32
+}
+
+impl Contact {
+    fn flipped(self) -> Self {
+        Contact {
+            normal: [-self.normal[0], -self.normal[1]],
+            depth: self.depth
+        }
+    }
+}
+
+// Touching edges don't count, so a body resolved flush against a wall stops colliding with it.
+pub fn contact(first: &Bounds, second: &Bounds) -> Option<Contact> {
+    match (first, second) {
+        (Bounds::Aabb { center: a, half_extents: a_half }, Bounds::Aabb { center: b, half_extents: b_half }) =>
+            aabb_contact(*a, *a_half, *b, *b_half),
+        (Bounds::Circle { center: a, radius: a_radius }, Bounds::Circle { center: b, radius: b_radius }) =>
+            circle_contact(*a, *a_radius, *b, *b_radius),
+        (Bounds::Aabb { center, half_extents }, Bounds::Circle { center: circle, radius }) =>
+            aabb_circle_contact(*center, *half_extents, *circle, *radius),
+        (Bounds::Circle { center: circle, radius }, Bounds::Aabb { center, half_extents }) =>
+            aabb_circle_contact(*center, *half_extents, *circle, *radius).map(Contact::flipped)
+    }
+}
+
+fn aabb_contact(a: [f32; 2], a_half: [f32; 2], b: [f32; 2], b_half: [f32; 2]) -> Option<Contact> {
+    let dx = b[0] - a[0];
+    let dy = b[1] - a[1];
+    let overlap_x = a_half[0] + b_half[0] - dx.abs();
+    let overlap_y = a_half[1] + b_half[1] - dy.abs();
+
+    if overlap_x <= 0.0 || overlap_y <= 0.0 {
+        return None;
+    }
+
+    // Push out along whichever axis needs the shorter move.
+    Some(if overlap_x < overlap_y {
+        Contact { normal: [-sign(dx), 0.0], depth: overlap_x }
+    } else {
+        Contact { normal: [0.0, -sign(dy)], depth: overlap_y }
+    })
+}
+
+fn circle_contact(a: [f32; 2], a_radius: f32, b: [f32; 2], b_radius: f32) -> Option<Contact> {
+    let dx = b[0] - a[0];
+    let dy = b[1] - a[1];
+    let distance = (dx * dx + dy * dy).sqrt();
+    let radii = a_radius + b_radius;
+
+    if distance >= radii {
+        return None;
+    }
+
+    // Concentric circles have no direction between them, so pick one.
+    let normal = if distance > 0.0 { [-dx / distance, -dy / distance] } else { [0.0, -1.0] };
+
+    Some(Contact { normal, depth: radii - distance })
+}
+
+fn aabb_circle_contact(center: [f32; 2], half_extents: [f32; 2], circle: [f32; 2], radius: f32) -> Option<Contact> {
+    let closest = [
+        circle[0].max(center[0] - half_extents[0]).min(center[0] + half_extents[0]),
+        circle[1].max(center[1] - half_extents[1]).min(center[1] + half_extents[1])
+    ];
+    let dx = circle[0] - closest[0];
+    let dy = circle[1] - closest[1];
+    let distance = (dx * dx + dy * dy).sqrt();
+
+    if distance >= radius {
+        return None;
+    }
+
+    if distance > 0.0 {
+        Some(Contact { normal: [-dx / distance, -dy / distance], depth: radius - distance })
+    } else {
+        // The circle's centre is inside the box: fall back to treating it as a square.
+        aabb_contact(center, half_extents, circle, [radius, radius])
+    }
+}
+
+fn sign(value: f32) -> f32 {
+    if value < 0.0 { -1.0 } else { 1.0 }
+}
+
+// A collider covering more cells than this goes in `oversized` instead, so one huge or runaway
+// shape can't fill the grid with millions of entries.
+const MAX_CELLS_PER_COLLIDER: i64 = 256;
+
+// Broad phase: only shapes sharing a grid cell, or paired with an oversized shape, are handed to `contact`.
+#[derive(Debug, Clone)]
+pub struct SpatialHash {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    oversized: Vec<usize>,
+    inserted: Vec<usize>
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: f32) -> Self {
+        SpatialHash {
+            cell_size: cell_size.max(1.0),
+            cells: HashMap::new(),
+            oversized: Vec::new(),
+            inserted: Vec::new()
+        }
+    }
+
+    pub fn insert(&mut self, index: usize, bounds: &Bounds) {
+        let (min, max) = bounds.extents();
+        let (min_x, min_y) = self.cell(min);
+        let (max_x, max_y) = self.cell(max);
+        self.inserted.push(index);
+
+        // Counted in i64 so colliders spanning the whole i32 range can't overflow.
+        let cell_count = (max_x as i64 - min_x as i64 + 1) * (max_y as i64 - min_y as i64 + 1);
+        if cell_count > MAX_CELLS_PER_COLLIDER {
+            self.oversized.push(index);
+            return;
+        }
+
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                self.cells.entry((x, y)).or_insert_with(Vec::new).push(index);
+            }
+        }
+    }
+
+    // Every pair of indices that share at least one cell, plus every oversized shape with every
+    // other shape, lowest first and in sorted order, so collision events come out the same way on every run.
+    pub fn candidate_pairs(&self) -> BTreeSet<(usize, usize)> {
+        let mut pairs = BTreeSet::new();
+        for indices in self.cells.values() {
+            for (position, first) in indices.iter().enumerate() {
+                for second in &indices[position + 1..] {
+                    pairs.insert((*first.min(second), *first.max(second)));
+                }
+            }
+        }
+
+        for first in &self.oversized {
+            for second in self.inserted.iter().filter(|second| *second != first) {
+                pairs.insert((*first.min(second), *first.max(second)));
+            }
+        }
+
+        pairs
+    }
+
+    fn cell(&self, point: [f32; 2]) -> (i32, i32) {
+        ((point[0] / self.cell_size).floor() as i32, (point[1] / self.cell_size).floor() as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use specs::prelude::*;
+
+    use crate::components::collider::{Collider, ColliderShape};
+    use crate::components::player_control::PlayerControl;
+    use crate::components::position::Position;
+    use crate::components::velocity::Velocity;
+    use crate::events::{GameEvent, GameEvents};
+    use crate::systems::detect_collisions::DetectCollisions;
+
+    fn aabb(center: [f32; 2], half: f32) -> Bounds {
+        Bounds::Aabb { center, half_extents: [half, half] }
+    }
+
+    fn circle(center: [f32; 2], radius: f32) -> Bounds {
+        Bounds::Circle { center, radius }
+    }
+
+    fn square_collider(size: f32) -> Collider {
+        Collider {
+            shape: ColliderShape::Aabb { width: size, height: size },
+            offset: [0.0, 0.0],
+            layers: 1,
+            mask: u32::MAX,
+            trigger: false
+        }
+    }
+
+    #[test]
+    fn aabb_contact_pushes_along_the_shallower_axis() {
+        let hit = contact(&aabb([0.0, 0.0], 10.0), &aabb([15.0, 2.0], 10.0));
+
+        assert_eq!(hit, Some(Contact { normal: [-1.0, 0.0], depth: 5.0 }));
+    }
+
+    #[test]
+    fn circle_contact_pushes_apart_along_the_centres() {
+        let hit = contact(&circle([0.0, 0.0], 10.0), &circle([0.0, 15.0], 10.0));
+
+        assert_eq!(hit, Some(Contact { normal: [0.0, -1.0], depth: 5.0 }));
+    }
+
+    #[test]
+    fn aabb_circle_contact_uses_the_closest_point_and_flips_for_circle_first() {
+        let hit = contact(&aabb([0.0, 0.0], 10.0), &circle([15.0, 0.0], 10.0));
+        assert_eq!(hit, Some(Contact { normal: [-1.0, 0.0], depth: 5.0 }));
+
+        let flipped = contact(&circle([15.0, 0.0], 10.0), &aabb([0.0, 0.0], 10.0));
+        assert_eq!(flipped, Some(Contact { normal: [1.0, 0.0], depth: 5.0 }));
+    }
+
+    #[test]
+    fn circle_centre_inside_box_falls_back_to_a_square() {
+        let hit = contact(&aabb([0.0, 0.0], 10.0), &circle([8.0, 0.0], 4.0));
+
+        assert_eq!(hit, Some(Contact { normal: [-1.0, 0.0], depth: 6.0 }));
+    }
+
+    #[test]
+    fn touching_edges_are_not_a_contact() {
+        assert_eq!(contact(&aabb([0.0, 0.0], 10.0), &aabb([20.0, 0.0], 10.0)), None);
+        assert_eq!(contact(&circle([0.0, 0.0], 10.0), &circle([20.0, 0.0], 10.0)), None);
+        assert_eq!(contact(&aabb([0.0, 0.0], 10.0), &circle([20.0, 0.0], 10.0)), None);
+    }
+
+    #[test]
+    fn candidate_pairs_include_shapes_spanning_several_cells_once() {
+        let mut hash = SpatialHash::new(64.0);
+        // Covers the four cells around (64, 64).
+        hash.insert(0, &aabb([64.0, 64.0], 10.0));
+        hash.insert(1, &aabb([100.0, 100.0], 5.0));
+        hash.insert(2, &aabb([10.0, 10.0], 5.0));
+        hash.insert(3, &circle([500.0, 500.0], 5.0));
+        // Shares all four cells with the first shape.
+        hash.insert(4, &aabb([64.0, 64.0], 20.0));
+
+        let pairs: Vec<(usize, usize)> = hash.candidate_pairs().into_iter().collect();
+
+        assert_eq!(pairs, vec![(0, 1), (0, 2), (0, 4), (1, 4), (2, 4)]);
+    }
+
+    #[test]
+    fn oversized_shapes_skip_the_grid_but_pair_with_everything() {
+        let mut hash = SpatialHash::new(64.0);
+        hash.insert(0, &aabb([0.0, 0.0], 5.0));
+        // Far past the cell cap, so it is never written cell by cell.
+        hash.insert(1, &aabb([0.0, 0.0], 1.0e9));
+        hash.insert(2, &circle([5000.0, -5000.0], 5.0));
+
+        assert!(hash.cells.values().all(|indices| !indices.contains(&1)));
+
+        let pairs: Vec<(usize, usize)> = hash.candidate_pairs().into_iter().collect();
+
+        assert_eq!(pairs, vec![(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn detect_collisions_pushes_player_out_of_a_solid_and_reports_it() {
+        let mut world = World::new();
+        let mut system = DetectCollisions;
+        System::setup(&mut system, &mut world);
+        let mut reader = world.fetch_mut::<GameEvents>().register_reader();
+
+        let player = world.create_entity()
+            .with(Position { x: 0.0, y: 0.0 })
+            .with(square_collider(20.0))
+            .with(PlayerControl {})
+            .with(Velocity { x: 5.0, y: 0.0 })
+            .build();
+        let wall = world.create_entity()
+            .with(Position { x: 15.0, y: 0.0 })
+            .with(square_collider(20.0))
+            .build();
+
+        system.run_now(&world);
+
+        let positions = world.read_storage::<Position>();
+        assert_eq!(positions.get(player), Some(&Position { x: -5.0, y: 0.0 }));
+        assert_eq!(positions.get(wall), Some(&Position { x: 15.0, y: 0.0 }));
+        assert_eq!(world.read_storage::<Velocity>().get(player), Some(&Velocity { x: 0.0, y: 0.0 }));
+
+        let events: Vec<GameEvent> = world.fetch::<GameEvents>().read(&mut reader).cloned().collect();
+        assert_eq!(events, vec![GameEvent::Collision { first: player, second: wall, trigger: false }]);
+    }
+}
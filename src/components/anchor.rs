@@ -0,0 +1,52 @@
+use specs::{Component, VecStorage};
+use crate::components::BasicTestComponent;
+use serde::{Deserialize, Serialize};
+
+pub const ANCHOR_LOAD_ID: &str = "anchor";
+
+#[derive(Deserialize, Serialize, Debug, Copy, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight
+}
+
+impl Default for Anchor {
+    fn default() -> Self {
+        Anchor::TopLeft
+    }
+}
+
+impl Anchor {
+    // The anchor's point on an area of `size`; a ScreenSpace entity's coordinates are offsets from it.
+    pub fn origin(&self, size: (f32, f32)) -> (f32, f32) {
+        let (fx, fy) = match self {
+            Anchor::TopLeft => (0.0, 0.0),
+            Anchor::Top => (0.5, 0.0),
+            Anchor::TopRight => (1.0, 0.0),
+            Anchor::Left => (0.0, 0.5),
+            Anchor::Center => (0.5, 0.5),
+            Anchor::Right => (1.0, 0.5),
+            Anchor::BottomLeft => (0.0, 1.0),
+            Anchor::Bottom => (0.5, 1.0),
+            Anchor::BottomRight => (1.0, 1.0)
+        };
+
+        (size.0 * fx, size.1 * fy)
+    }
+}
+
+impl Component for Anchor {
+    type Storage = VecStorage<Self>;
+}
+
+impl BasicTestComponent for Anchor {
+    const LOAD_ID: &'static str = ANCHOR_LOAD_ID;
+}
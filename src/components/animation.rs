@@ -1,100 +1,59 @@
-use specs::{Component, DenseVecStorage, World};
+use specs::{Component, VecStorage};
+use crate::components::BasicTestComponent;
+use crate::components::sprite::default_scale;
+use serde::{Deserialize, Serialize};
 
-use coffee::graphics::{Image, Sprite, Point, Rectangle, Window};
-use coffee::load::Task;
+pub const ANIMATION_LOAD_ID: &str = "animation";
 
-use crate::components::position::Position;
-use crate::globals::ImageDict;
-
-use serde::{Deserialize};
-use crate::components::ComponentLoader;
-
-pub const ANIMATION_FILE_ID: &str = "animation";
-
-#[derive(Deserialize, Debug, Clone)]
-struct AnimationLoader {
-    pub image: String,
-    pub current_frame: u16,
-    pub start_frame: u16,
-    pub end_frame: u16,
-    pub total_frames: u16,
-    pub dimensions_x: u16,
-    pub dimensions_y: u16,
-    pub scale_x: f32,
-    pub scale_y: f32
-}
-
-#[derive(Debug, Clone)]
+// Plays frames of `frame_size` cut from an atlas region, holding each for `frame_pause` ticks.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct Animation {
-    pub image: Image,
-    pub current_frame: u16, // frames are 1-indexed
+    pub region: String,
+    pub frame_size: [u16; 2],
+    #[serde(default)]
     pub start_frame: u16,
     pub end_frame: u16,
-    pub total_frames: u16,
-    pub dimensions: (u16, u16),
-    pub scale: (f32, f32),
+    #[serde(default = "default_frame_pause")]
+    pub frame_pause: u16,
+    #[serde(default)]
+    pub current_frame: u16,
+    #[serde(default)]
+    pub ticks: u16,
+    #[serde(default)]
+    pub offset: [f32; 2],
+    #[serde(default = "default_scale")]
+    pub scale: [f32; 2]
 }
 
-impl Component for Animation {
-    type Storage = DenseVecStorage<Self>;
+fn default_frame_pause() -> u16 {
+    1
 }
 
-impl ComponentLoader for AnimationLoader {
-    type ComponentType = Animation;
-
-    fn load_component(self, ecs: &mut World, window: &Window) -> Task<Self::ComponentType> {
-        let image = ecs.get_mut::<ImageDict>().unwrap()
-            .0
-            .get(self.image.as_str());
-
-        if let Some(handle) = image {
-            Task::new(|| { Ok(
-                Animation {
-                    image: handle.clone(),
-                    current_frame: self.current_frame,
-                    start_frame: self.start_frame,
-                    end_frame: self.end_frame,
-                    total_frames: self.total_frames,
-                    dimensions: (self.dimensions_x, self.dimensions_y),
-                    scale: (self.scale_x, self.scale_y)
-                })
-            })
-        } else {
-            Image::load(self.image)
-                .map(|new_image| {
-                    Animation {
-                        image: new_image,
-                        current_frame: self.current_frame,
-                        start_frame: self.start_frame,
-                        end_frame: self.end_frame,
-                        total_frames: self.total_frames,
-                        dimensions: (self.dimensions_x, self.dimensions_y),
-                        scale: (self.scale_x, self.scale_y)
-                    }
-                })
-        }
+impl Animation {
+    pub fn advance(&mut self) {
+        step_frame(&mut self.ticks, &mut self.current_frame, self.start_frame, self.end_frame, self.frame_pause);
     }
 }
 
-impl Animation {
-    pub fn create_sprite(&mut self, pos: &Position) -> Sprite {
-        let frame_width = self.dimensions.0/self.total_frames;
-        let frame_height = self.dimensions.1;
-        let frame_x = frame_width * (self.current_frame - 1);
-        let frame_y = pos.y;
+// Holds each frame for `frame_pause` ticks, then moves on, wrapping from end_frame back to start_frame.
+pub fn step_frame(ticks: &mut u16, current_frame: &mut u16, start_frame: u16, end_frame: u16, frame_pause: u16) {
+    *ticks += 1;
+    if *ticks < frame_pause {
+        return
+    }
 
+    *ticks = 0;
+    *current_frame = if *current_frame < start_frame || *current_frame >= end_frame {
+        start_frame
+    } else {
+        *current_frame + 1
+    };
+}
 
-        self.current_frame += 1;
+impl Component for Animation {
+    type Storage = VecStorage<Self>;
+}
 
-        Sprite {
-            source: Rectangle {
-                x: frame_x,
-                y: frame_y,
-                width: frame_width,
-                height: frame_height,
-            },
-            position: Point::new(pos.x.into(), pos.y.into()),
-            scale: self.scale,
-        }
-    }
+impl BasicTestComponent for Animation {
+    const LOAD_ID: &'static str = ANIMATION_LOAD_ID;
 }
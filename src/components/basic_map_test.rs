@@ -1,11 +1,11 @@
 use std::collections::HashMap;
 use specs::{Component, VecStorage};
 use crate::components::BasicTestComponent;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 pub const BASIC_MAP_TEST_COMPONENT_LOAD_ID: &str = "basic_map_test_component";
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct BasicMapTest {
     pub map: HashMap<u32, String>
 }
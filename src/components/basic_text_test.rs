@@ -1,10 +1,10 @@
 use specs::{Component, VecStorage};
 use crate::components::{BasicTestComponent};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 pub const BASIC_TEXT_TEST_COMPONENT_LOAD_ID: &str = "basic_text_test_component";
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct BasicTextTest {
     pub text: String
 }
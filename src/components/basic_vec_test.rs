@@ -1,10 +1,10 @@
 use specs::{Component, VecStorage};
 use crate::components::BasicTestComponent;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 pub const BASIC_VECTOR_TEST_COMPONENT_LOAD_ID: &str = "basic_vector_test_component";
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct BasicVectorTest {
     pub vector: Vec<u32>
 }
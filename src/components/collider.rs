@@ -0,0 +1,73 @@
+use specs::{Component, VecStorage};
+use crate::components::BasicTestComponent;
+use crate::components::position::Position;
+use crate::collision::Bounds;
+use serde::{Deserialize, Serialize};
+
+pub const COLLIDER_LOAD_ID: &str = "collider";
+
+#[derive(Deserialize, Serialize, Debug, Copy, Clone, PartialEq)]
+#[serde(tag = "shape", rename_all = "snake_case")]
+pub enum ColliderShape {
+    Aabb {
+        width: f32,
+        height: f32
+    },
+    Circle {
+        radius: f32
+    }
+}
+
+// Centred on the entity's Position plus `offset`. `layers` are the bits this collider is on and
+// `mask` the bits it collides with; both sides have to accept each other. Triggers only report
+// overlaps, solids also push PlayerControl entities back out.
+#[derive(Deserialize, Serialize, Debug, Copy, Clone, PartialEq)]
+pub struct Collider {
+    #[serde(flatten)]
+    pub shape: ColliderShape,
+    #[serde(default)]
+    pub offset: [f32; 2],
+    #[serde(default = "default_layers")]
+    pub layers: u32,
+    #[serde(default = "default_mask")]
+    pub mask: u32,
+    #[serde(default)]
+    pub trigger: bool
+}
+
+fn default_layers() -> u32 {
+    1
+}
+
+fn default_mask() -> u32 {
+    u32::MAX
+}
+
+impl Collider {
+    pub fn bounds(&self, position: &Position) -> Bounds {
+        let center = [position.x + self.offset[0], position.y + self.offset[1]];
+
+        match self.shape {
+            ColliderShape::Aabb { width, height } => Bounds::Aabb {
+                center,
+                half_extents: [width / 2.0, height / 2.0]
+            },
+            ColliderShape::Circle { radius } => Bounds::Circle {
+                center,
+                radius
+            }
+        }
+    }
+
+    pub fn interacts_with(&self, other: &Collider) -> bool {
+        self.layers & other.mask != 0 && other.layers & self.mask != 0
+    }
+}
+
+impl Component for Collider {
+    type Storage = VecStorage<Self>;
+}
+
+impl BasicTestComponent for Collider {
+    const LOAD_ID: &'static str = COLLIDER_LOAD_ID;
+}
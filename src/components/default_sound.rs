@@ -0,0 +1,25 @@
+use specs::{Component, DenseVecStorage};
+use crate::components::BasicTestComponent;
+use crate::audio::Channel;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_SOUND_LOAD_ID: &str = "default_sound";
+
+// The engine's DefaultSound format, played through the AudioPlayer so the volume settings apply.
+// Plays `sound_name`, one of the scene's declared sounds, each time `play_flag` is set.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct DefaultSound {
+    pub sound_name: String,
+    #[serde(default)]
+    pub channel: Channel,
+    #[serde(default)]
+    pub play_flag: bool
+}
+
+impl Component for DefaultSound {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl BasicTestComponent for DefaultSound {
+    const LOAD_ID: &'static str = DEFAULT_SOUND_LOAD_ID;
+}
@@ -0,0 +1,270 @@
+use specs::{Component, World, Builder};
+use specs::storage::DenseVecStorage;
+
+use coffee::graphics::{Mesh, Window, Shape, Rectangle, Color, Point, HorizontalAlignment, VerticalAlignment};
+
+use serde::Deserialize;
+use crate::components::{ComponentLoader, BuildComponent};
+use crate::components::text_display::TextDisplay;
+use crate::components::animation::step_frame;
+use game_engine::load::JSONLoad;
+use specs::world::LazyBuilder;
+
+use anyhow::Result;
+use crate::globals::convert_json;
+use crate::globals::TestGlobalError::LoadIDMatchError;
+
+pub const DRAWABLE_LOAD_ID: &str = "drawable";
+
+// The engine's drawable format: groups of shapes, lines of text and image strips on one entity.
+// The engine's own loader needs the window and the wholesale font and image dicts, so the format is
+// loaded here instead and drawn by DrawWorld, under the camera and the entity's RenderLayer.
+#[derive(Deserialize, Debug, Clone)]
+struct DrawableJSON {
+    #[serde(default)]
+    shapes: Vec<ShapeGroupJSON>,
+    #[serde(default)]
+    text: Vec<DrawableTextJSON>,
+    #[serde(default)]
+    animated_sprites: Vec<AnimatedSpriteJSON>
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct ShapeGroupJSON {
+    shapes: Vec<PaintedShapeJSON>
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct PaintedShapeJSON {
+    shape: ShapeJSON,
+    description: PaintJSON
+}
+
+#[derive(Deserialize, Debug, Copy, Clone)]
+enum ShapeJSON {
+    Rectangle { x: f32, y: f32, width: f32, height: f32 },
+    Circle { x: f32, y: f32, radius: f32 }
+}
+
+#[derive(Deserialize, Debug, Copy, Clone)]
+struct PaintJSON {
+    r: f32,
+    g: f32,
+    b: f32,
+    a: f32,
+    paint_type: PaintTypeJSON
+}
+
+#[derive(Deserialize, Debug, Copy, Clone)]
+enum PaintTypeJSON {
+    Fill,
+    Stroke { width: f32 }
+}
+
+#[derive(Deserialize, Debug, Copy, Clone)]
+enum HorizontalAlignJSON {
+    Left,
+    Center,
+    Right
+}
+
+#[derive(Deserialize, Debug, Copy, Clone)]
+enum VerticalAlignJSON {
+    Top,
+    Center,
+    Bottom
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct DrawableTextJSON {
+    content: Vec<String>,
+    #[serde(default)]
+    content_index: usize,
+    position_x: f32,
+    position_y: f32,
+    bounds_x: f32,
+    bounds_y: f32,
+    size: f32,
+    r: f32,
+    g: f32,
+    b: f32,
+    a: f32,
+    h_align: HorizontalAlignJSON,
+    v_align: VerticalAlignJSON,
+    font: String
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct AnimatedSpriteJSON {
+    x: u16,
+    y: u16,
+    width: u16,
+    height: u16,
+    position: [f32; 2],
+    scale: [f32; 2],
+    start_frame: u16,
+    end_frame: u16,
+    frame_pause: u16,
+    image: String
+}
+
+// One strip of frames laid out left to right in a scene image, starting at `source`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrawableSprite {
+    pub image: String,
+    pub source: Rectangle<u16>,
+    pub position: [f32; 2],
+    pub scale: [f32; 2],
+    pub start_frame: u16,
+    pub end_frame: u16,
+    pub frame_pause: u16,
+    pub current_frame: u16,
+    pub ticks: u16
+}
+
+impl DrawableSprite {
+    pub fn advance(&mut self) {
+        step_frame(&mut self.ticks, &mut self.current_frame, self.start_frame, self.end_frame, self.frame_pause);
+    }
+
+    pub fn frame_source(&self) -> Rectangle<u16> {
+        Rectangle {
+            x: self.source.x + self.current_frame * self.source.width,
+            ..self.source
+        }
+    }
+}
+
+// Shapes are baked into one mesh; text and sprites are queued into the scene's fonts and images when drawn.
+#[derive(Debug)]
+pub struct Drawable {
+    pub mesh: Option<Mesh>,
+    pub texts: Vec<TextDisplay>,
+    pub sprites: Vec<DrawableSprite>
+}
+
+impl Component for Drawable {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl From<&DrawableJSON> for Drawable {
+    fn from(json: &DrawableJSON) -> Self {
+        let mut mesh = Mesh::new();
+        let mut has_shapes = false;
+        for painted in json.shapes.iter().flat_map(|group| group.shapes.iter()) {
+            let shape = match painted.shape {
+                ShapeJSON::Rectangle { x, y, width, height } => Shape::Rectangle(Rectangle { x, y, width, height }),
+                ShapeJSON::Circle { x, y, radius } => Shape::Circle { center: Point::new(x, y), radius }
+            };
+            let paint = painted.description;
+            let color = Color::new(paint.r, paint.g, paint.b, paint.a);
+
+            match paint.paint_type {
+                PaintTypeJSON::Fill => mesh.fill(shape, color),
+                PaintTypeJSON::Stroke { width } => mesh.stroke(shape, color, width)
+            }
+            has_shapes = true;
+        }
+
+        // Only the line at content_index is shown, as the engine's drawable did.
+        let texts = json.text.iter()
+            .map(|text| TextDisplay {
+                content: text.content.get(text.content_index).cloned().into_iter().collect(),
+                position: Point::new(text.position_x, text.position_y),
+                bounds: (text.bounds_x, text.bounds_y),
+                size: text.size,
+                color: Color::new(text.r, text.g, text.b, text.a),
+                h_align: match text.h_align {
+                    HorizontalAlignJSON::Left => HorizontalAlignment::Left,
+                    HorizontalAlignJSON::Center => HorizontalAlignment::Center,
+                    HorizontalAlignJSON::Right => HorizontalAlignment::Right
+                },
+                v_align: match text.v_align {
+                    VerticalAlignJSON::Top => VerticalAlignment::Top,
+                    VerticalAlignJSON::Center => VerticalAlignment::Center,
+                    VerticalAlignJSON::Bottom => VerticalAlignment::Bottom
+                },
+                font: text.font.clone()
+            })
+            .collect();
+
+        let sprites = json.animated_sprites.iter()
+            .map(|sprite| DrawableSprite {
+                image: sprite.image.clone(),
+                source: Rectangle { x: sprite.x, y: sprite.y, width: sprite.width, height: sprite.height },
+                position: sprite.position,
+                scale: sprite.scale,
+                start_frame: sprite.start_frame,
+                end_frame: sprite.end_frame,
+                frame_pause: sprite.frame_pause,
+                current_frame: sprite.start_frame,
+                ticks: 0
+            })
+            .collect();
+
+        Drawable {
+            mesh: if has_shapes { Some(mesh) } else { None },
+            texts,
+            sprites
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DrawableLoader {
+    drawable_json: DrawableJSON
+}
+
+impl ComponentLoader for DrawableLoader {
+    fn from_json(json: JSONLoad) -> Result<Self> where Self: Sized {
+        return if json.load_type_id == DRAWABLE_LOAD_ID {
+            let drawable_json: DrawableJSON = convert_json(&json.actual_value, stringify!(DrawableJSON))?;
+
+            Ok(DrawableLoader{drawable_json})
+        } else {
+            Err(
+                anyhow::Error::new(
+                    LoadIDMatchError {
+                        expected_id: DRAWABLE_LOAD_ID.to_string(),
+                        actual_id: json.load_type_id
+                    }
+                )
+            )
+        }
+    }
+
+    fn load_component<'a>(&self, builder: LazyBuilder<'a>, ecs: &World, _window: &Window) -> Result<LazyBuilder<'a>> {
+        self.build_component(builder, ecs)
+    }
+
+    fn set_value(&mut self, new_value: JSONLoad) -> Result<()> {
+        if new_value.load_type_id == DRAWABLE_LOAD_ID {
+            self.drawable_json = convert_json(&new_value.actual_value, stringify!(DrawableJSON))?;
+
+            Ok(())
+        } else {
+            Err(
+                anyhow::Error::new(LoadIDMatchError {
+                    expected_id: DRAWABLE_LOAD_ID.to_string(),
+                    actual_id: new_value.load_type_id
+                })
+            )
+        }
+    }
+
+    fn get_component_name(&self) -> String {
+        return "Drawable".to_string()
+    }
+}
+
+impl BuildComponent for DrawableLoader {
+    fn build_component<'a>(&self, builder: LazyBuilder<'a>, _ecs: &World) -> Result<LazyBuilder<'a>> {
+        let drawable: Drawable = (&self.drawable_json).into();
+
+        Ok(builder.with(drawable))
+    }
+
+    fn into_loader(self: Box<Self>) -> Box<dyn ComponentLoader> {
+        self
+    }
+}
@@ -0,0 +1,20 @@
+use specs::{Component, VecStorage};
+use crate::components::BasicTestComponent;
+use serde::{Deserialize, Serialize};
+
+pub const LOCAL_OFFSET_LOAD_ID: &str = "local_offset";
+
+// A child's Position relative to its Parent's, kept up to date by the propagate_transforms system.
+#[derive(Deserialize, Serialize, Debug, Copy, Clone, Default, PartialEq)]
+pub struct LocalOffset {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Component for LocalOffset {
+    type Storage = VecStorage<Self>;
+}
+
+impl BasicTestComponent for LocalOffset {
+    const LOAD_ID: &'static str = LOCAL_OFFSET_LOAD_ID;
+}
@@ -5,13 +5,13 @@ use coffee::graphics::{Mesh, Window, Shape, Rectangle, Color};
 use coffee::load::Task;
 
 use serde::Deserialize;
-use crate::components::ComponentLoader;
+use crate::components::{ComponentLoader, BuildComponent};
 use game_engine::load::JSONLoad;
 use specs::world::LazyBuilder;
 
 use anyhow::Result;
-use serde_json::from_value;
-use crate::globals::TestGlobalError::{ConvertJSONError, LoadIDMatchError};
+use crate::globals::convert_json;
+use crate::globals::TestGlobalError::LoadIDMatchError;
 use game_engine::load::LoadError::LoadIDError;
 
 pub const MESH_GRAPHIC_LOAD_ID: &str = "mesh_graphic";
@@ -29,25 +29,66 @@ struct MeshGraphicJSON {
     pub stroke_width: f32,
 }
 
+// Keeps the rectangle it was built from, since coffee meshes can't be edited after they're built.
 #[derive(Debug)]
 pub struct MeshGraphic {
-    pub mesh: Mesh
+    pub mesh: Mesh,
+    bounds: Rectangle<f32>,
+    color: Color,
+    stroke_width: f32
 }
 
 impl Component for MeshGraphic {
     type Storage = DenseVecStorage<Self>;
 }
 
+impl MeshGraphic {
+    pub fn new(bounds: Rectangle<f32>, color: Color, stroke_width: f32) -> Self {
+        let mut mesh_graphic = MeshGraphic {
+            mesh: Mesh::new(),
+            bounds,
+            color,
+            stroke_width
+        };
+        mesh_graphic.rebuild();
+
+        mesh_graphic
+    }
+
+    pub fn bounds(&self) -> Rectangle<f32> {
+        self.bounds
+    }
+
+    pub fn color(&self) -> Color {
+        self.color
+    }
+
+    pub fn set_bounds(&mut self, bounds: Rectangle<f32>) {
+        self.bounds = bounds;
+        self.rebuild();
+    }
+
+    pub fn set_color(&mut self, color: Color) {
+        self.color = color;
+        self.rebuild();
+    }
+
+    fn rebuild(&mut self) {
+        let mut mesh = Mesh::new();
+        mesh.stroke(Shape::Rectangle(self.bounds), self.color, self.stroke_width);
+        self.mesh = mesh;
+    }
+}
+
 impl From<MeshGraphicJSON> for MeshGraphic {
     fn from(mesh_json: MeshGraphicJSON) -> Self {
-        let mut mesh = Mesh::new();
-        mesh.stroke(
-            Shape::Rectangle(Rectangle {
+        MeshGraphic::new(
+            Rectangle {
                 x: mesh_json.x,
                 y: mesh_json.y,
                 width: mesh_json.width,
                 height: mesh_json.height
-            }),
+            },
             Color::new(
                 mesh_json.r,
                 mesh_json.g,
@@ -55,11 +96,7 @@ impl From<MeshGraphicJSON> for MeshGraphic {
                 mesh_json.a
             ),
             mesh_json.stroke_width
-        );
-
-        MeshGraphic {
-            mesh
-        }
+        )
     }
 }
 
@@ -71,17 +108,8 @@ pub struct MeshGraphicLoader {
 impl ComponentLoader for MeshGraphicLoader {
     fn from_json(json: JSONLoad) -> Result<Self> where Self: Sized {
         return if json.load_type_id == MESH_GRAPHIC_LOAD_ID {
-            let mesh_json: MeshGraphicJSON = from_value(json.actual_value.clone())
-                .map_err(|e| {
-                    anyhow::Error::new(
-                        ConvertJSONError {
-                            value: json.actual_value,
-                            into_type: "MeshGraphicJSON".to_string(),
-                            source: e
-                        }
-                    )
-                })?;
-            
+            let mesh_json: MeshGraphicJSON = convert_json(&json.actual_value, stringify!(MeshGraphicJSON))?;
+
             Ok(MeshGraphicLoader{mesh_json})
         } else {
             Err(
@@ -95,24 +123,13 @@ impl ComponentLoader for MeshGraphicLoader {
         }
     }
 
-    fn load_component<'a>(&self, builder: LazyBuilder<'a>, ecs: &World, window: &Window) -> Result<LazyBuilder<'a>> {
-        let mesh_graphic: MeshGraphic = self.mesh_json.into();
-
-        Ok(builder.with(mesh_graphic))
+    fn load_component<'a>(&self, builder: LazyBuilder<'a>, ecs: &World, _window: &Window) -> Result<LazyBuilder<'a>> {
+        self.build_component(builder, ecs)
     }
 
     fn set_value(&mut self, new_value: JSONLoad) -> Result<()> {
         if new_value.load_type_id == MESH_GRAPHIC_LOAD_ID {
-            self.mesh_json = from_value(new_value.actual_value.clone())
-                .map_err(|e| {
-                    anyhow::Error::new(
-                        ConvertJSONError {
-                            value: new_value.actual_value,
-                            into_type: stringify!(MeshGraphicJSON).to_string(),
-                            source: e
-                        }
-                    )
-                })?;
+            self.mesh_json = convert_json(&new_value.actual_value, stringify!(MeshGraphicJSON))?;
 
             Ok(())
         } else {
@@ -128,4 +145,16 @@ impl ComponentLoader for MeshGraphicLoader {
     fn get_component_name(&self) -> String {
         return "Mesh Graphic".to_string()
     }
-}
\ No newline at end of file
+}
+
+impl BuildComponent for MeshGraphicLoader {
+    fn build_component<'a>(&self, builder: LazyBuilder<'a>, _ecs: &World) -> Result<LazyBuilder<'a>> {
+        let mesh_graphic: MeshGraphic = self.mesh_json.into();
+
+        Ok(builder.with(mesh_graphic))
+    }
+
+    fn into_loader(self: Box<Self>) -> Box<dyn ComponentLoader> {
+        self
+    }
+}
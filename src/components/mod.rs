@@ -4,9 +4,28 @@ pub mod basic_vec_test;
 pub mod basic_text_test;
 pub mod basic_map_test;
 pub mod mesh_graphic;
+pub mod drawable;
 pub mod text_display;
+pub mod position;
+pub mod player_control;
+pub mod screen_space;
+pub mod anchor;
+pub mod sprite;
+pub mod animation;
+pub mod tilemap;
+pub mod collider;
+pub mod velocity;
+pub mod rigid_body;
+pub mod particle_emitter;
+pub mod tween;
+pub mod render_layer;
+pub mod parent;
+pub mod local_offset;
+pub mod previous_position;
+pub mod default_sound;
+pub mod registry;
 
-use game_engine::components::{ComponentMux, ComponentLoader, drawables::{DRAWABLE_LOAD_ID, Drawable, DrawableLoader}};
+use game_engine::components::{ComponentMux, ComponentLoader};
 use game_engine::load::JSONLoad;
 
 use anyhow::Result;
@@ -15,44 +34,90 @@ use specs::{Component, VecStorage, EntityBuilder, World, Builder, WorldExt};
 use std::fmt::Debug;
 use serde::Deserialize;
 use std::collections::HashMap;
-use serde_json::{Value, from_value};
+use serde_json::Value;
 use std::marker::PhantomData;
 use std::sync::{Arc, RwLock};
 use coffee::graphics::Window;
-use crate::globals::TestGlobalError::{LoadIDMatchError, ConvertJSONError};
+use crate::globals::TestGlobalError::LoadIDMatchError;
+use crate::globals::convert_json;
 use crate::components::basic_number_test::{BasicNumberTest, BASIC_NUMBER_TEST_COMPONENT_LOAD_ID};
 use crate::components::basic_boolean_test::{BasicBooleanTest, BASIC_BOOLEAN_TEST_COMPONENT_LOAD_ID};
 use crate::components::basic_vec_test::{BasicVectorTest, BASIC_VECTOR_TEST_COMPONENT_LOAD_ID};
 use crate::components::basic_text_test::{BasicTextTest, BASIC_TEXT_TEST_COMPONENT_LOAD_ID};
 use crate::components::basic_map_test::{BasicMapTest, BASIC_MAP_TEST_COMPONENT_LOAD_ID};
 use crate::components::mesh_graphic::{MESH_GRAPHIC_LOAD_ID, MeshGraphicLoader, MeshGraphic};
+use crate::components::drawable::{DRAWABLE_LOAD_ID, DrawableLoader};
 use crate::components::text_display::{TEXT_DISPLAY_FILE_ID, TextDisplay, TextDisplayLoader};
 use crate::components::ComponentError::ComponentPoisonError;
+use crate::components::position::{Position, POSITION_LOAD_ID};
+use crate::components::player_control::{PlayerControl, PLAYER_CONTROL_LOAD_ID};
+use crate::components::screen_space::{ScreenSpace, SCREEN_SPACE_LOAD_ID};
+use crate::components::anchor::{Anchor, ANCHOR_LOAD_ID};
+use crate::components::sprite::{AtlasSprite, ATLAS_SPRITE_LOAD_ID};
+use crate::components::animation::{Animation, ANIMATION_LOAD_ID};
+use crate::components::tilemap::{TilemapLoader, TILEMAP_LOAD_ID};
+use crate::components::collider::{Collider, COLLIDER_LOAD_ID};
+use crate::components::velocity::{Velocity, VELOCITY_LOAD_ID};
+use crate::components::rigid_body::{RigidBody, RIGID_BODY_LOAD_ID};
+use crate::components::particle_emitter::{ParticleEmitter, PARTICLE_EMITTER_LOAD_ID};
+use crate::components::tween::{Tween, TWEEN_LOAD_ID};
+use crate::components::render_layer::{RenderLayer, RENDER_LAYER_LOAD_ID};
+use crate::components::local_offset::{LocalOffset, LOCAL_OFFSET_LOAD_ID};
 use specs::world::LazyBuilder;
-use game_engine::components::audibles::default_sound::{DefaultSoundLoader, DEFAULT_SOUND_LOAD_ID};
+use crate::components::default_sound::{DefaultSound, DEFAULT_SOUND_LOAD_ID};
 
 pub struct BasicTestComponentMux {}
 
 impl ComponentMux for BasicTestComponentMux {
     fn map_json_to_loader(json: JSONLoad) -> Result<Box<dyn ComponentLoader>> {
+        Self::map_json_to_builder(json).map(|builder| builder.into_loader())
+    }
+}
+
+impl BasicTestComponentMux {
+    pub fn map_json_to_builder(json: JSONLoad) -> Result<Box<dyn BuildComponent>> {
         return match json.load_type_id.as_str() {
             BASIC_NUMBER_TEST_COMPONENT_LOAD_ID => Ok(Box::new(BasicTestComponentLoader::<BasicNumberTest>::from_json(json)?)),
             BASIC_BOOLEAN_TEST_COMPONENT_LOAD_ID => Ok(Box::new(BasicTestComponentLoader::<BasicBooleanTest>::from_json(json)?)),
             BASIC_TEXT_TEST_COMPONENT_LOAD_ID => Ok(Box::new(BasicTestComponentLoader::<BasicTextTest>::from_json(json)?)),
             BASIC_VECTOR_TEST_COMPONENT_LOAD_ID => Ok(Box::new(BasicTestComponentLoader::<BasicVectorTest>::from_json(json)?)),
             BASIC_MAP_TEST_COMPONENT_LOAD_ID => Ok(Box::new(BasicTestComponentLoader::<BasicMapTest>::from_json(json)?)),
+            POSITION_LOAD_ID => Ok(Box::new(BasicTestComponentLoader::<Position>::from_json(json)?)),
+            PLAYER_CONTROL_LOAD_ID => Ok(Box::new(BasicTestComponentLoader::<PlayerControl>::from_json(json)?)),
+            SCREEN_SPACE_LOAD_ID => Ok(Box::new(BasicTestComponentLoader::<ScreenSpace>::from_json(json)?)),
+            ANCHOR_LOAD_ID => Ok(Box::new(BasicTestComponentLoader::<Anchor>::from_json(json)?)),
+            ATLAS_SPRITE_LOAD_ID => Ok(Box::new(BasicTestComponentLoader::<AtlasSprite>::from_json(json)?)),
+            ANIMATION_LOAD_ID => Ok(Box::new(BasicTestComponentLoader::<Animation>::from_json(json)?)),
+            COLLIDER_LOAD_ID => Ok(Box::new(BasicTestComponentLoader::<Collider>::from_json(json)?)),
+            VELOCITY_LOAD_ID => Ok(Box::new(BasicTestComponentLoader::<Velocity>::from_json(json)?)),
+            RIGID_BODY_LOAD_ID => Ok(Box::new(BasicTestComponentLoader::<RigidBody>::from_json(json)?)),
+            PARTICLE_EMITTER_LOAD_ID => Ok(Box::new(BasicTestComponentLoader::<ParticleEmitter>::from_json(json)?)),
+            TWEEN_LOAD_ID => Ok(Box::new(BasicTestComponentLoader::<Tween>::from_json(json)?)),
+            RENDER_LAYER_LOAD_ID => Ok(Box::new(BasicTestComponentLoader::<RenderLayer>::from_json(json)?)),
+            LOCAL_OFFSET_LOAD_ID => Ok(Box::new(BasicTestComponentLoader::<LocalOffset>::from_json(json)?)),
+            DEFAULT_SOUND_LOAD_ID => Ok(Box::new(BasicTestComponentLoader::<DefaultSound>::from_json(json)?)),
+            MESH_GRAPHIC_LOAD_ID => Ok(Box::new(MeshGraphicLoader::from_json(json)?)),
+            TEXT_DISPLAY_FILE_ID => Ok(Box::new(TextDisplayLoader::from_json(json)?)),
+            TILEMAP_LOAD_ID => Ok(Box::new(TilemapLoader::from_json(json)?)),
             DRAWABLE_LOAD_ID => Ok(Box::new(DrawableLoader::from_json(json)?)),
-            DEFAULT_SOUND_LOAD_ID=> Ok(Box::new(DefaultSoundLoader::from_json(json)?)),
             _ => Err(anyhow::Error::new(
                 LoadIDMatchError {
                     expected_id: "Expected one of basic test components load IDs".to_string(),
-                    actual_id: "".to_string()
+                    actual_id: json.load_type_id.clone()
                 }
             ))
         }
     }
 }
 
+// The engine's ComponentLoader::load_component also takes the window, which none of these
+// components needs. Entities are built through this instead, so they can be built without one.
+pub trait BuildComponent: ComponentLoader {
+    fn build_component<'b>(&self, builder: LazyBuilder<'b>, ecs: &World) -> Result<LazyBuilder<'b>>;
+
+    fn into_loader(self: Box<Self>) -> Box<dyn ComponentLoader>;
+}
+
 pub trait BasicTestComponent: Component + Debug + Send + Sync {
     const LOAD_ID: &'static str;
 }
@@ -83,21 +148,8 @@ impl<T: BasicTestComponent + for<'de> Deserialize<'de>> ComponentLoader for Basi
         }
     }
 
-    fn load_component<'b>(&self, builder: LazyBuilder<'b>, ecs: &World, window: &Window) -> Result<LazyBuilder<'b>> {
-        Ok(
-            builder.with(from_value::<T>(self.cached_value.clone())
-                .map_err(|e| {
-                        anyhow::Error::new(
-                            ConvertJSONError {
-                                value: self.cached_value.clone(),
-                                into_type: "BasicTestComponentLoader::T".to_string(),
-                                source: e
-                            }
-                        )
-                    }
-                )?
-            )
-        )
+    fn load_component<'b>(&self, builder: LazyBuilder<'b>, ecs: &World, _window: &Window) -> Result<LazyBuilder<'b>> {
+        self.build_component(builder, ecs)
     }
 
     fn set_value(&mut self, new_value: JSONLoad) -> Result<()> {
@@ -120,6 +172,19 @@ impl<T: BasicTestComponent + for<'de> Deserialize<'de>> ComponentLoader for Basi
     }
 }
 
+impl<T: BasicTestComponent + for<'de> Deserialize<'de>> BuildComponent for BasicTestComponentLoader<T>
+    where <T as specs::Component>::Storage: std::default::Default {
+    fn build_component<'b>(&self, builder: LazyBuilder<'b>, _ecs: &World) -> Result<LazyBuilder<'b>> {
+        Ok(
+            builder.with(convert_json::<T>(&self.cached_value, std::any::type_name::<T>())?)
+        )
+    }
+
+    fn into_loader(self: Box<Self>) -> Box<dyn ComponentLoader> {
+        self
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum ComponentError {
     #[error("Failed to get lock of variable: {var_name:?}.")]
@@ -0,0 +1,36 @@
+use specs::{Component, VecStorage, Entity, World, WorldExt, Join};
+
+use std::collections::HashSet;
+
+// Set by the entity loader on inline children; there's no JSON form since entity ids only exist at runtime.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Parent {
+    pub entity: Entity
+}
+
+impl Component for Parent {
+    type Storage = VecStorage<Self>;
+}
+
+// `roots` plus every entity below them, each listed once.
+pub fn with_descendants(ecs: &World, roots: Vec<Entity>) -> Vec<Entity> {
+    let parents = ecs.read_storage::<Parent>();
+    let entities = ecs.entities();
+
+    let mut found: HashSet<Entity> = roots.iter().copied().collect();
+    let mut all = roots;
+    let mut frontier = all.clone();
+
+    while !frontier.is_empty() {
+        let children: Vec<Entity> = (&entities, &parents).join()
+            .filter(|(child, parent)| frontier.contains(&parent.entity) && !found.contains(child))
+            .map(|(child, _)| child)
+            .collect();
+
+        found.extend(children.iter().copied());
+        all.extend(children.iter().copied());
+        frontier = children;
+    }
+
+    all
+}
@@ -0,0 +1,280 @@
+use specs::{Component, DenseVecStorage};
+use crate::components::BasicTestComponent;
+use serde::{Deserialize, Serialize};
+
+use rand::Rng;
+
+use std::fmt::{Debug, Formatter};
+
+pub const PARTICLE_EMITTER_LOAD_ID: &str = "particle_emitter";
+
+const DEFAULT_MAX_PARTICLES: usize = 256;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Particle {
+    pub position: [f32; 2],
+    pub velocity: [f32; 2],
+    pub age: f32,
+    pub lifetime: f32
+}
+
+impl Particle {
+    // How far through its life the particle is, from 0 to 1.
+    pub fn progress(&self) -> f32 {
+        if self.lifetime > 0.0 { (self.age / self.lifetime).min(1.0) } else { 1.0 }
+    }
+}
+
+// Emits `rate` particles a second from the entity's Position plus `offset`. Ranges are [min, max]
+// and picked from with the GameRng, so a seeded run emits the same particles. Particles with an
+// `image` (an image_dict name the scene declares) are drawn as scaled sprites; without one they're
+// filled squares of `size` pixels, and only those can show `start_color`/`end_color`, since coffee
+// sprites can't be tinted.
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub struct ParticleEmitter {
+    #[serde(default)]
+    pub image: Option<String>,
+    pub rate: f32,
+    pub lifetime: [f32; 2],
+    pub velocity_x: [f32; 2],
+    pub velocity_y: [f32; 2],
+    #[serde(default = "default_color")]
+    pub start_color: [f32; 4],
+    #[serde(default = "default_color")]
+    pub end_color: [f32; 4],
+    #[serde(default = "default_scale")]
+    pub start_scale: f32,
+    #[serde(default = "default_scale")]
+    pub end_scale: f32,
+    #[serde(default = "default_size")]
+    pub size: f32,
+    #[serde(default)]
+    pub offset: [f32; 2],
+    #[serde(default = "default_max_particles")]
+    pub max_particles: usize,
+    #[serde(default = "default_emitting")]
+    pub emitting: bool,
+    #[serde(skip)]
+    pub particles: Vec<Particle>,
+    #[serde(skip)]
+    pending: f32
+}
+
+fn default_color() -> [f32; 4] {
+    [1.0, 1.0, 1.0, 1.0]
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+fn default_size() -> f32 {
+    4.0
+}
+
+fn default_max_particles() -> usize {
+    DEFAULT_MAX_PARTICLES
+}
+
+fn default_emitting() -> bool {
+    true
+}
+
+impl ParticleEmitter {
+    // Ages and moves the live particles, then spawns this tick's share of `rate` at `origin`.
+    pub fn update<R: Rng>(&mut self, origin: [f32; 2], dt: f32, rng: &mut R) {
+        for particle in self.particles.iter_mut() {
+            particle.age += dt;
+            particle.position[0] += particle.velocity[0] * dt;
+            particle.position[1] += particle.velocity[1] * dt;
+        }
+        self.particles.retain(|particle| particle.age < particle.lifetime);
+
+        if !self.emitting {
+            self.pending = 0.0;
+            return;
+        }
+
+        self.pending += self.rate * dt;
+        while self.pending >= 1.0 {
+            self.pending -= 1.0;
+
+            if self.particles.len() >= self.max_particles {
+                continue;
+            }
+
+            let particle = Particle {
+                position: [origin[0] + self.offset[0], origin[1] + self.offset[1]],
+                velocity: [pick(rng, self.velocity_x), pick(rng, self.velocity_y)],
+                age: 0.0,
+                lifetime: pick(rng, self.lifetime)
+            };
+            self.particles.push(particle);
+        }
+    }
+
+    pub fn color(&self, particle: &Particle) -> [f32; 4] {
+        let t = particle.progress();
+        let mut color = [0.0; 4];
+        for (channel, value) in color.iter_mut().enumerate() {
+            *value = lerp(self.start_color[channel], self.end_color[channel], t);
+        }
+
+        color
+    }
+
+    pub fn scale(&self, particle: &Particle) -> f32 {
+        lerp(self.start_scale, self.end_scale, particle.progress())
+    }
+}
+
+fn pick<R: Rng>(rng: &mut R, range: [f32; 2]) -> f32 {
+    if range[1] > range[0] { rng.gen_range(range[0]..range[1]) } else { range[0] }
+}
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
+
+// The live particles would swamp the inspector, so only their count is shown.
+impl Debug for ParticleEmitter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParticleEmitter")
+            .field("image", &self.image)
+            .field("rate", &self.rate)
+            .field("emitting", &self.emitting)
+            .field("particles", &self.particles.len())
+            .finish()
+    }
+}
+
+impl Component for ParticleEmitter {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl BasicTestComponent for ParticleEmitter {
+    const LOAD_ID: &'static str = PARTICLE_EMITTER_LOAD_ID;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    const SEED: u64 = 7;
+
+    fn emitter(rate: f32, lifetime: f32) -> ParticleEmitter {
+        ParticleEmitter {
+            image: None,
+            rate,
+            lifetime: [lifetime, lifetime],
+            velocity_x: [-10.0, 10.0],
+            velocity_y: [-20.0, 0.0],
+            start_color: default_color(),
+            end_color: default_color(),
+            start_scale: default_scale(),
+            end_scale: default_scale(),
+            size: default_size(),
+            offset: [0.0, 0.0],
+            max_particles: DEFAULT_MAX_PARTICLES,
+            emitting: true,
+            particles: Vec::new(),
+            pending: 0.0
+        }
+    }
+
+    fn run(emitter: &mut ParticleEmitter, updates: usize, dt: f32, rng: &mut StdRng) {
+        for _ in 0..updates {
+            emitter.update([0.0, 0.0], dt, rng);
+        }
+    }
+
+    #[test]
+    fn emits_rate_particles_per_second_carrying_fractions_over() {
+        let mut rng = StdRng::seed_from_u64(SEED);
+        let mut emitter = emitter(2.0, 100.0);
+
+        run(&mut emitter, 1, 0.25, &mut rng);
+        assert_eq!(emitter.particles.len(), 0);
+
+        run(&mut emitter, 1, 0.25, &mut rng);
+        assert_eq!(emitter.particles.len(), 1);
+
+        run(&mut emitter, 6, 0.25, &mut rng);
+        assert_eq!(emitter.particles.len(), 4);
+    }
+
+    #[test]
+    fn particles_start_at_the_origin_with_velocities_in_range() {
+        let mut rng = StdRng::seed_from_u64(SEED);
+        let mut emitter = emitter(4.0, 100.0);
+        emitter.offset = [1.0, 2.0];
+
+        emitter.update([10.0, 20.0], 1.0, &mut rng);
+
+        assert_eq!(emitter.particles.len(), 4);
+        for particle in &emitter.particles {
+            assert_eq!(particle.position, [11.0, 22.0]);
+            assert!(particle.velocity[0] >= -10.0 && particle.velocity[0] < 10.0);
+            assert!(particle.velocity[1] >= -20.0 && particle.velocity[1] < 0.0);
+        }
+    }
+
+    #[test]
+    fn same_seed_emits_the_same_particles() {
+        let mut first = emitter(8.0, 100.0);
+        let mut second = emitter(8.0, 100.0);
+
+        run(&mut first, 4, 0.25, &mut StdRng::seed_from_u64(SEED));
+        run(&mut second, 4, 0.25, &mut StdRng::seed_from_u64(SEED));
+
+        assert_eq!(first.particles, second.particles);
+    }
+
+    #[test]
+    fn particles_expire_at_the_end_of_their_lifetime() {
+        let mut rng = StdRng::seed_from_u64(SEED);
+        let mut emitter = emitter(4.0, 0.5);
+
+        // One particle a tick, each living two ticks.
+        run(&mut emitter, 3, 0.25, &mut rng);
+        assert_eq!(emitter.particles.len(), 2);
+        assert!(emitter.particles.iter().all(|particle| particle.age < particle.lifetime));
+
+        emitter.emitting = false;
+        run(&mut emitter, 2, 0.25, &mut rng);
+        assert!(emitter.particles.is_empty());
+    }
+
+    #[test]
+    fn emission_stops_at_max_particles() {
+        let mut rng = StdRng::seed_from_u64(SEED);
+        let mut emitter = emitter(100.0, 100.0);
+        emitter.max_particles = 5;
+
+        run(&mut emitter, 2, 1.0, &mut rng);
+
+        assert_eq!(emitter.particles.len(), 5);
+        assert_eq!(emitter.pending, 0.0);
+    }
+
+    #[test]
+    fn color_and_scale_lerp_over_the_particle_lifetime() {
+        let mut emitter = emitter(0.0, 2.0);
+        emitter.start_color = [1.0, 0.0, 0.0, 1.0];
+        emitter.end_color = [0.0, 0.0, 1.0, 0.0];
+        emitter.start_scale = 1.0;
+        emitter.end_scale = 3.0;
+
+        let mut particle = Particle { position: [0.0, 0.0], velocity: [0.0, 0.0], age: 0.5, lifetime: 2.0 };
+        assert_eq!(emitter.color(&particle), [0.75, 0.0, 0.25, 0.75]);
+        assert_eq!(emitter.scale(&particle), 1.5);
+
+        // Progress is clamped, so an overdue particle stays at the end values.
+        particle.age = 3.0;
+        assert_eq!(emitter.color(&particle), [0.0, 0.0, 1.0, 0.0]);
+        assert_eq!(emitter.scale(&particle), 3.0);
+    }
+}
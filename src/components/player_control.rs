@@ -1,27 +1,16 @@
-// use specs::{Component, World};
-// use specs::storage::HashMapStorage;
-//
-// use coffee::graphics::Window;
-// use coffee::load::Task;
-//
-// use serde::Deserialize;
-// use crate::components::ComponentLoader;
-//
-// pub const PLAYER_CONTROL_FILE_ID: &str = "player_control";
-//
-// #[derive(Deserialize, Debug)]
-// pub struct PlayerControl {}
-//
-// impl Component for PlayerControl {
-//     type Storage = HashMapStorage<Self>;
-// }
-//
-// impl ComponentLoader for PlayerControl {
-//     type ComponentType = Self;
-//
-//     fn load_component(self, ecs: &mut World, window: &Window) -> Task<Self::ComponentType> {
-//         Task::new(|| {
-//             Ok(PlayerControl{})
-//         })
-//     }
-// }
\ No newline at end of file
+use specs::{Component, NullStorage};
+use crate::components::BasicTestComponent;
+use serde::{Deserialize, Serialize};
+
+pub const PLAYER_CONTROL_LOAD_ID: &str = "player_control";
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct PlayerControl {}
+
+impl Component for PlayerControl {
+    type Storage = NullStorage<Self>;
+}
+
+impl BasicTestComponent for PlayerControl {
+    const LOAD_ID: &'static str = PLAYER_CONTROL_LOAD_ID;
+}
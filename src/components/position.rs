@@ -1,32 +1,19 @@
-// use specs::{Component, World};
-// use specs::storage::VecStorage;
-//
-// use coffee::graphics::Window;
-// use coffee::load::Task;
-//
-// use serde::Deserialize;
-// use crate::components::ComponentLoader;
-//
-// pub const POSITION_FILE_ID: &str = "position";
-//
-// #[derive(Deserialize, Debug)]
-// pub struct Position {
-//     pub x: u16,
-//     pub y: u16,
-// }
-//
-// impl Component for Position {
-//     type Storage = VecStorage<Self>;
-// }
-//
-// impl ComponentLoader for Position {
-//     type ComponentType = Self;
-//
-//     fn load_component(self, _ecs: &mut World, _window: &Window) -> Task<Self::ComponentType> {
-//         Task::new(|| {
-//             Ok(self)
-//         })
-//     }
-// }
-//
-// // impl ComponentLoader<VecStorage<Position>> for Position {}
\ No newline at end of file
+use specs::{Component, VecStorage};
+use crate::components::BasicTestComponent;
+use serde::{Deserialize, Serialize};
+
+pub const POSITION_LOAD_ID: &str = "position";
+
+#[derive(Deserialize, Serialize, Debug, Copy, Clone, Default, PartialEq)]
+pub struct Position {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Component for Position {
+    type Storage = VecStorage<Self>;
+}
+
+impl BasicTestComponent for Position {
+    const LOAD_ID: &'static str = POSITION_LOAD_ID;
+}
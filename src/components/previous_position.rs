@@ -0,0 +1,28 @@
+use specs::{Component, VecStorage};
+use crate::components::position::Position;
+
+// Position as it was before the latest tick, recorded by the scene so draws can blend towards the
+// current one. Like Parent it has no JSON form; every entity with a Position gets one on its first tick.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct PreviousPosition {
+    pub x: f32,
+    pub y: f32
+}
+
+impl PreviousPosition {
+    // Where `current` should be drawn `t` of the way through the next tick. Without a previous
+    // position (e.g. spawned since the last tick) it's drawn where it is.
+    pub fn interpolate(previous: Option<&PreviousPosition>, current: &Position, t: f32) -> Position {
+        match previous {
+            Some(previous) => Position {
+                x: previous.x + (current.x - previous.x) * t,
+                y: previous.y + (current.y - previous.y) * t
+            },
+            None => *current
+        }
+    }
+}
+
+impl Component for PreviousPosition {
+    type Storage = VecStorage<Self>;
+}
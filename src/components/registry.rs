@@ -0,0 +1,136 @@
+use specs::{Component, World, WorldExt, Entity};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, to_value};
+
+use anyhow::Result;
+use thiserror::Error;
+
+use std::fmt::Debug;
+
+use game_engine::load::JSONLoad;
+
+use crate::globals::{merge_json, convert_json};
+use crate::components::registry::RegistryError::{NotEditableError, MissingComponentError, SerializeComponentError};
+
+type DescribeFn = fn(&World, Entity) -> Option<String>;
+type EditFn = fn(&World, Entity, &Value) -> Result<()>;
+
+pub struct RegisteredComponent {
+    pub name: &'static str,
+    pub load_id: Option<&'static str>,
+    describe: DescribeFn,
+    edit: Option<EditFn>
+}
+
+// specs has no reflection, so every component the inspector should see is registered here by hand.
+#[derive(Default)]
+pub struct ComponentRegistry {
+    components: Vec<RegisteredComponent>
+}
+
+impl ComponentRegistry {
+    pub fn register<T>(&mut self, ecs: &mut World, name: &'static str)
+        where T: Component + Debug, <T as Component>::Storage: Default {
+        ecs.register::<T>();
+
+        self.components.push(RegisteredComponent {
+            name,
+            load_id: None,
+            describe: describe_component::<T>,
+            edit: None
+        });
+    }
+
+    pub fn register_editable<T>(&mut self, ecs: &mut World, name: &'static str, load_id: &'static str)
+        where T: Component + Debug + Serialize + DeserializeOwned, <T as Component>::Storage: Default {
+        ecs.register::<T>();
+
+        self.components.push(RegisteredComponent {
+            name,
+            load_id: Some(load_id),
+            describe: describe_component::<T>,
+            edit: Some(edit_component::<T>)
+        });
+    }
+
+    pub fn describe_entity(&self, ecs: &World, entity: Entity) -> Vec<(&'static str, String)> {
+        self.components.iter()
+            .filter_map(|component| {
+                (component.describe)(ecs, entity).map(|description| (component.name, description))
+            })
+            .collect()
+    }
+
+    // Edits use the same envelope as ComponentLoader::set_value, with actual_value as a merge patch.
+    pub fn apply_edit(&self, ecs: &World, entity: Entity, edit: &JSONLoad) -> Result<()> {
+        let component = self.components.iter()
+            .find(|component| component.load_id == Some(edit.load_type_id.as_str()))
+            .ok_or_else(|| {
+                anyhow::Error::new(NotEditableError {
+                    load_type_id: edit.load_type_id.clone()
+                })
+            })?;
+
+        match component.edit {
+            Some(edit_fn) => edit_fn(ecs, entity, &edit.actual_value),
+            None => Err(anyhow::Error::new(NotEditableError {
+                load_type_id: edit.load_type_id.clone()
+            }))
+        }
+    }
+}
+
+fn describe_component<T: Component + Debug>(ecs: &World, entity: Entity) -> Option<String> {
+    ecs.read_storage::<T>()
+        .get(entity)
+        .map(|component| format!("{:?}", component))
+}
+
+fn edit_component<T>(ecs: &World, entity: Entity, patch: &Value) -> Result<()>
+    where T: Component + Serialize + DeserializeOwned {
+    let mut storage = ecs.write_storage::<T>();
+
+    let current = storage.get(entity)
+        .ok_or_else(|| {
+            anyhow::Error::new(MissingComponentError {
+                entity_id: entity.id(),
+                component_type: std::any::type_name::<T>().to_string()
+            })
+        })?;
+
+    let mut value = to_value(current)
+        .map_err(|e| {
+            anyhow::Error::new(SerializeComponentError {
+                component_type: std::any::type_name::<T>().to_string(),
+                source: e
+            })
+        })?;
+
+    merge_json(&mut value, patch);
+
+    let edited: T = convert_json(&value, std::any::type_name::<T>())?;
+
+    storage.insert(entity, edited)?;
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum RegistryError {
+    #[error("No editable component registered for load_type_id: {load_type_id}")]
+    NotEditableError {
+        load_type_id: String
+    },
+    #[error("Entity {entity_id} has no {component_type} component")]
+    MissingComponentError {
+        entity_id: u32,
+        component_type: String
+    },
+    #[error("Failed to serialize {component_type}")]
+    SerializeComponentError {
+        component_type: String,
+        source: serde_json::error::Error
+    }
+}
@@ -0,0 +1,22 @@
+use specs::{Component, VecStorage};
+use crate::components::BasicTestComponent;
+use serde::{Deserialize, Serialize};
+
+pub const RENDER_LAYER_LOAD_ID: &str = "render_layer";
+
+// Which of the scene's named layers the entity's graphics draw in, and their order within it.
+// Entities without one draw in the scene's default layer at z 0.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct RenderLayer {
+    pub layer: String,
+    #[serde(default)]
+    pub z: f32
+}
+
+impl Component for RenderLayer {
+    type Storage = VecStorage<Self>;
+}
+
+impl BasicTestComponent for RenderLayer {
+    const LOAD_ID: &'static str = RENDER_LAYER_LOAD_ID;
+}
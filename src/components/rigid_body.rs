@@ -0,0 +1,134 @@
+use specs::{Component, VecStorage};
+use crate::components::BasicTestComponent;
+use serde::{Deserialize, Serialize};
+
+pub const RIGID_BODY_LOAD_ID: &str = "rigid_body";
+
+// Makes a Velocity entity feel gravity, forces and drag. `drag` slows it in proportion to its speed,
+// `restitution` is how much of its speed it keeps when bouncing off a solid collider, and `friction`
+// slows it while it slides along one.
+#[derive(Deserialize, Serialize, Debug, Copy, Clone, PartialEq)]
+pub struct RigidBody {
+    #[serde(default = "default_mass")]
+    pub mass: f32,
+    #[serde(default = "default_gravity_scale")]
+    pub gravity_scale: f32,
+    #[serde(default)]
+    pub drag: f32,
+    #[serde(default)]
+    pub restitution: f32,
+    #[serde(default)]
+    pub friction: f32,
+    // Accumulated by apply_force during a tick and cleared once integrated.
+    #[serde(skip)]
+    pub force: [f32; 2]
+}
+
+fn default_mass() -> f32 {
+    1.0
+}
+
+fn default_gravity_scale() -> f32 {
+    1.0
+}
+
+impl Default for RigidBody {
+    fn default() -> Self {
+        RigidBody {
+            mass: default_mass(),
+            gravity_scale: default_gravity_scale(),
+            drag: 0.0,
+            restitution: 0.0,
+            friction: 0.0,
+            force: [0.0, 0.0]
+        }
+    }
+}
+
+impl RigidBody {
+    pub fn apply_force(&mut self, force: [f32; 2]) {
+        self.force[0] += force[0];
+        self.force[1] += force[1];
+    }
+
+    // The acceleration this tick: gravity plus accumulated force over mass. A massless body
+    // ignores forces rather than dividing by zero.
+    pub fn acceleration(&self, gravity: [f32; 2]) -> [f32; 2] {
+        let inverse_mass = if self.mass > 0.0 { 1.0 / self.mass } else { 0.0 };
+
+        [
+            gravity[0] * self.gravity_scale + self.force[0] * inverse_mass,
+            gravity[1] * self.gravity_scale + self.force[1] * inverse_mass
+        ]
+    }
+}
+
+impl Component for RigidBody {
+    type Storage = VecStorage<Self>;
+}
+
+impl BasicTestComponent for RigidBody {
+    const LOAD_ID: &'static str = RIGID_BODY_LOAD_ID;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use specs::prelude::*;
+
+    use crate::components::position::Position;
+    use crate::components::velocity::Velocity;
+    use crate::physics::Gravity;
+    use crate::systems::integrate_physics::IntegratePhysics;
+    use crate::timestep::DeltaTime;
+
+    fn body(mass: f32) -> RigidBody {
+        RigidBody { mass, ..RigidBody::default() }
+    }
+
+    #[test]
+    fn mass_scales_the_acceleration_from_a_force() {
+        let mut light = body(1.0);
+        let mut heavy = body(4.0);
+        light.apply_force([8.0, -4.0]);
+        heavy.apply_force([8.0, -4.0]);
+
+        assert_eq!(light.acceleration([0.0, 0.0]), [8.0, -4.0]);
+        assert_eq!(heavy.acceleration([0.0, 0.0]), [2.0, -1.0]);
+    }
+
+    #[test]
+    fn gravity_ignores_mass_and_massless_bodies_ignore_forces() {
+        let mut massless = body(0.0);
+        massless.apply_force([100.0, 0.0]);
+
+        assert_eq!(body(3.0).acceleration([0.0, 10.0]), [0.0, 10.0]);
+        assert_eq!(massless.acceleration([0.0, 10.0]), [0.0, 10.0]);
+    }
+
+    #[test]
+    fn integrating_applies_forces_once_and_clears_them() {
+        let mut world = World::new();
+        world.register::<RigidBody>();
+        world.register::<Velocity>();
+        world.register::<Position>();
+        world.insert(DeltaTime(0.5));
+        world.insert(Gravity::default());
+
+        let mut pushed = body(2.0);
+        pushed.apply_force([4.0, 0.0]);
+        let entity = world.create_entity()
+            .with(pushed)
+            .with(Velocity::default())
+            .with(Position { x: 0.0, y: 0.0 })
+            .build();
+
+        IntegratePhysics.run_now(&world);
+        IntegratePhysics.run_now(&world);
+
+        // 4 / 2 = 2 px/s² for the first half second only.
+        assert_eq!(*world.read_storage::<Velocity>().get(entity).unwrap(), Velocity { x: 1.0, y: 0.0 });
+        assert_eq!(world.read_storage::<RigidBody>().get(entity).unwrap().force, [0.0, 0.0]);
+    }
+}
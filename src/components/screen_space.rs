@@ -0,0 +1,17 @@
+use specs::{Component, NullStorage};
+use crate::components::BasicTestComponent;
+use serde::{Deserialize, Serialize};
+
+pub const SCREEN_SPACE_LOAD_ID: &str = "screen_space";
+
+// Marks UI that is drawn in window pixels and ignores the camera.
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct ScreenSpace {}
+
+impl Component for ScreenSpace {
+    type Storage = NullStorage<Self>;
+}
+
+impl BasicTestComponent for ScreenSpace {
+    const LOAD_ID: &'static str = SCREEN_SPACE_LOAD_ID;
+}
@@ -0,0 +1,27 @@
+use specs::{Component, VecStorage};
+use crate::components::BasicTestComponent;
+use serde::{Deserialize, Serialize};
+
+pub const ATLAS_SPRITE_LOAD_ID: &str = "atlas_sprite";
+
+// A whole atlas region, drawn at the entity's Position plus `offset`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct AtlasSprite {
+    pub region: String,
+    #[serde(default)]
+    pub offset: [f32; 2],
+    #[serde(default = "default_scale")]
+    pub scale: [f32; 2]
+}
+
+pub fn default_scale() -> [f32; 2] {
+    [1.0, 1.0]
+}
+
+impl Component for AtlasSprite {
+    type Storage = VecStorage<Self>;
+}
+
+impl BasicTestComponent for AtlasSprite {
+    const LOAD_ID: &'static str = ATLAS_SPRITE_LOAD_ID;
+}
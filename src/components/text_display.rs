@@ -5,10 +5,10 @@ use coffee::graphics::{Point, Color, HorizontalAlignment, VerticalAlignment, Win
 // use coffee::load::Task;
 use anyhow::Result;
 use serde::Deserialize;
-use crate::components::ComponentLoader;
+use crate::components::{ComponentLoader, BuildComponent};
 use game_engine::load::JSONLoad;
-use crate::globals::TestGlobalError::{LoadIDMatchError, ConvertJSONError};
-use serde_json::from_value;
+use crate::globals::TestGlobalError::LoadIDMatchError;
+use crate::globals::convert_json;
 use specs::world::LazyBuilder;
 
 pub const TEXT_DISPLAY_FILE_ID: &str = "text_display";
@@ -75,16 +75,7 @@ pub struct TextDisplayLoader {
 impl ComponentLoader for TextDisplayLoader {
     fn from_json(json: JSONLoad) -> Result<Self> where Self: Sized {
         if json.load_type_id == TEXT_DISPLAY_FILE_ID {
-            let text_display_json = from_value(json.actual_value.clone())
-                .map_err(|e| {
-                    anyhow::Error::new(
-                        ConvertJSONError {
-                            value: json.actual_value,
-                            into_type: stringify!(TextdisplayJSON).to_string(),
-                            source: e
-                        }
-                    )
-                })?;
+            let text_display_json = convert_json(&json.actual_value, stringify!(TextDisplayJSON))?;
 
             Ok(TextDisplayLoader {
                 text_display_json
@@ -100,19 +91,12 @@ impl ComponentLoader for TextDisplayLoader {
     }
 
     fn load_component<'a>(&self, builder: LazyBuilder<'a>, ecs: &World, _window: &Window) -> Result<LazyBuilder<'a>> {
-        Ok(builder.with(TextDisplay::from(self.text_display_json.clone())))
+        self.build_component(builder, ecs)
     }
 
     fn set_value(&mut self, new_value: JSONLoad) -> Result<()> {
         if new_value.load_type_id == TEXT_DISPLAY_FILE_ID {
-            self.text_display_json = from_value(new_value.actual_value.clone())
-                .map_err(|e| {
-                    ConvertJSONError {
-                        value: new_value.actual_value,
-                        into_type: stringify!(TextDisplayJSON).to_string(),
-                        source: e
-                    }
-                })?;
+            self.text_display_json = convert_json(&new_value.actual_value, stringify!(TextDisplayJSON))?;
 
             Ok(())
         } else {
@@ -130,4 +114,14 @@ impl ComponentLoader for TextDisplayLoader {
     fn get_component_name(&self) -> String {
         return TEXT_DISPLAY_FILE_ID.to_string()
     }
-}
\ No newline at end of file
+}
+
+impl BuildComponent for TextDisplayLoader {
+    fn build_component<'a>(&self, builder: LazyBuilder<'a>, _ecs: &World) -> Result<LazyBuilder<'a>> {
+        Ok(builder.with(TextDisplay::from(self.text_display_json.clone())))
+    }
+
+    fn into_loader(self: Box<Self>) -> Box<dyn ComponentLoader> {
+        self
+    }
+}
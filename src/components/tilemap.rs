@@ -0,0 +1,432 @@
+use specs::{Component, World, Builder};
+use specs::storage::DenseVecStorage;
+use specs::world::LazyBuilder;
+
+use coffee::graphics::{Window, Rectangle};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use anyhow::Result;
+use thiserror::Error;
+
+use std::fmt::{Debug, Formatter};
+
+use game_engine::load::JSONLoad;
+
+use crate::components::{ComponentLoader, BuildComponent};
+use crate::entities::ComponentJSON;
+use crate::globals::{load_json, convert_json, JSONSource, LoadErrors};
+use crate::globals::TestGlobalError::LoadIDMatchError;
+use crate::components::tilemap::TilemapError::{UnsupportedMapFormatError, TileLayerSizeError, ObjectPropertyError, MissingObjectTypeError, UndeclaredTilesetError, TilesetSizeError};
+
+pub const TILEMAP_LOAD_ID: &str = "tilemap";
+
+// Tiled stores flip flags in the top bits of each gid; flipped tiles are drawn unflipped for now.
+const TILED_GID_MASK: u32 = 0x1FFF_FFFF;
+
+// coffee sprite sources are u16, so tiles past this in a tileset image can't be addressed.
+const MAX_TILESET_SIZE: u32 = u16::MAX as u32;
+
+#[derive(Deserialize, Debug, Clone)]
+struct TilemapJSON {
+    map: String
+}
+
+// The subset of Tiled's JSON map format the game uses. Export maps from Tiled as JSON, not TMX.
+#[derive(Deserialize, Debug)]
+struct TiledMapJSON {
+    tilewidth: u32,
+    tileheight: u32,
+    layers: Vec<TiledLayerJSON>,
+    tilesets: Vec<TiledTilesetJSON>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum TiledLayerJSON {
+    Tilelayer {
+        name: String,
+        width: u32,
+        height: u32,
+        data: Vec<u32>,
+        #[serde(default = "default_visible")]
+        visible: bool,
+        #[serde(default)]
+        offsetx: f32,
+        #[serde(default)]
+        offsety: f32
+    },
+    Objectgroup {
+        name: String,
+        objects: Vec<TiledObjectJSON>
+    },
+    #[serde(other)]
+    Unsupported
+}
+
+fn default_visible() -> bool {
+    true
+}
+
+#[derive(Deserialize, Debug)]
+struct TiledObjectJSON {
+    #[serde(default)]
+    name: String,
+    // Tiled 1.9 renamed `type` to `class`; either names the prefab to spawn.
+    #[serde(default, alias = "class")]
+    r#type: String,
+    x: f32,
+    y: f32,
+    #[serde(default)]
+    properties: Vec<TiledPropertyJSON>
+}
+
+#[derive(Deserialize, Debug)]
+struct TiledPropertyJSON {
+    name: String,
+    value: Value
+}
+
+// `name` must match an image_dict entry the scene declares; tilesets in external .tsx files aren't supported.
+#[derive(Deserialize, Debug)]
+struct TiledTilesetJSON {
+    firstgid: u32,
+    name: String,
+    tilewidth: u32,
+    tileheight: u32,
+    columns: u32,
+    imagewidth: u32,
+    imageheight: u32,
+    #[serde(default)]
+    margin: u32,
+    #[serde(default)]
+    spacing: u32
+}
+
+#[derive(Debug, Clone)]
+pub struct Tileset {
+    pub first_gid: u32,
+    pub image: String,
+    pub tile_size: [u32; 2],
+    pub columns: u32,
+    pub margin: u32,
+    pub spacing: u32
+}
+
+impl Tileset {
+    // Tilemap::load rejects tileset images over MAX_TILESET_SIZE, so a tile inside one fits in u16.
+    pub fn source(&self, gid: u32) -> Rectangle<u16> {
+        let index = gid - self.first_gid;
+        let column = index % self.columns.max(1);
+        let row = index / self.columns.max(1);
+
+        Rectangle {
+            x: (self.margin + column * (self.tile_size[0] + self.spacing)) as u16,
+            y: (self.margin + row * (self.tile_size[1] + self.spacing)) as u16,
+            width: self.tile_size[0] as u16,
+            height: self.tile_size[1] as u16
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TileLayer {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub tiles: Vec<u32>,
+    pub offset: [f32; 2]
+}
+
+// An object layer entry: spawned from the prefab named by its type, relative to the tilemap's Position.
+#[derive(Debug, Clone)]
+pub struct TileObject {
+    pub prefab_id: String,
+    pub position: [f32; 2],
+    pub overrides: Vec<ComponentJSON>
+}
+
+#[derive(Clone)]
+pub struct Tilemap {
+    pub tile_size: [u32; 2],
+    pub layers: Vec<TileLayer>,
+    pub tilesets: Vec<Tileset>,
+    pub objects: Vec<TileObject>,
+    pub objects_spawned: bool
+}
+
+// Summarised so the inspector doesn't print every tile.
+impl Debug for Tilemap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Tilemap")
+            .field("tile_size", &self.tile_size)
+            .field("layers", &self.layers.iter().map(|layer| layer.name.as_str()).collect::<Vec<_>>())
+            .field("tilesets", &self.tilesets.iter().map(|tileset| tileset.image.as_str()).collect::<Vec<_>>())
+            .field("objects", &self.objects.len())
+            .field("objects_spawned", &self.objects_spawned)
+            .finish()
+    }
+}
+
+impl Component for Tilemap {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl Tilemap {
+    // Tilesets are sorted by first_gid, so the owner of a gid is the last one starting at or before it.
+    pub fn tileset(&self, gid: u32) -> Option<&Tileset> {
+        self.tilesets.iter().rev().find(|tileset| tileset.first_gid <= gid)
+    }
+
+    // Every non-empty tile as (tileset, source rectangle, position relative to the map origin).
+    pub fn tiles(&self) -> impl Iterator<Item = (&Tileset, Rectangle<u16>, [f32; 2])> + '_ {
+        let [tile_width, tile_height] = self.tile_size;
+
+        self.layers.iter().flat_map(move |layer| {
+            layer.tiles.iter().enumerate().filter_map(move |(index, raw_gid)| {
+                let gid = raw_gid & TILED_GID_MASK;
+                if gid == 0 {
+                    return None
+                }
+
+                let column = index as u32 % layer.width;
+                let row = index as u32 / layer.width;
+
+                self.tileset(gid).map(|tileset| {
+                    (
+                        tileset,
+                        tileset.source(gid),
+                        [
+                            layer.offset[0] + (column * tile_width) as f32,
+                            // Tiles taller than the grid hang upwards from their cell, as in Tiled.
+                            layer.offset[1] + ((row + 1) * tile_height) as f32 - tileset.tile_size[1] as f32
+                        ]
+                    )
+                })
+            })
+        })
+    }
+
+    // Tilesets draw from the image sharing their name, which only loads if the scene declares it.
+    pub fn check_tilesets(&self, images: &[String]) -> Result<()> {
+        let mut errors = LoadErrors::default();
+
+        for tileset in &self.tilesets {
+            if !images.contains(&tileset.image) {
+                errors.push(anyhow::Error::new(UndeclaredTilesetError {
+                    tileset: tileset.image.clone()
+                }));
+            }
+        }
+
+        errors.into_result(())
+    }
+
+    pub fn load(file_path: &str) -> Result<Self> {
+        if file_path.ends_with(".tmx") {
+            return Err(anyhow::Error::new(UnsupportedMapFormatError {
+                file_path: file_path.to_string()
+            }))
+        }
+
+        let map: TiledMapJSON = load_json(file_path)?;
+        let mut errors = LoadErrors::default();
+
+        let mut tilesets: Vec<Tileset> = Vec::new();
+        for (index, tileset) in map.tilesets.into_iter().enumerate() {
+            if tileset.imagewidth > MAX_TILESET_SIZE || tileset.imageheight > MAX_TILESET_SIZE {
+                let source = JSONSource::new(file_path, format!("/tilesets/{}", index).as_str());
+                errors.push(source.locate(anyhow::Error::new(TilesetSizeError {
+                    tileset: tileset.name,
+                    width: tileset.imagewidth,
+                    height: tileset.imageheight,
+                    limit: MAX_TILESET_SIZE
+                })));
+                continue;
+            }
+
+            tilesets.push(Tileset {
+                first_gid: tileset.firstgid,
+                image: tileset.name,
+                tile_size: [tileset.tilewidth, tileset.tileheight],
+                columns: tileset.columns,
+                margin: tileset.margin,
+                spacing: tileset.spacing
+            });
+        }
+        tilesets.sort_by_key(|tileset| tileset.first_gid);
+
+        let mut layers = Vec::new();
+        let mut objects = Vec::new();
+
+        for (index, layer) in map.layers.into_iter().enumerate() {
+            let source = JSONSource::new(file_path, format!("/layers/{}", index).as_str());
+
+            match layer {
+                TiledLayerJSON::Tilelayer { name, width, height, data, visible, offsetx, offsety } => {
+                    let expected = width.checked_mul(height).map(|tiles| tiles as usize);
+                    if expected != Some(data.len()) {
+                        errors.push(source.join("/data").locate(anyhow::Error::new(TileLayerSizeError {
+                            layer: name,
+                            width,
+                            height,
+                            actual: data.len()
+                        })));
+                        continue;
+                    }
+
+                    if visible {
+                        layers.push(TileLayer { name, width, height, tiles: data, offset: [offsetx, offsety] });
+                    }
+                },
+                TiledLayerJSON::Objectgroup { objects: layer_objects, .. } => {
+                    for (object_index, object) in layer_objects.into_iter().enumerate() {
+                        let object_source = source.join(format!("/objects/{}", object_index).as_str());
+                        if let Some(object) = errors.collect(TileObject::from_tiled(object, &object_source)) {
+                            objects.push(object);
+                        }
+                    }
+                },
+                TiledLayerJSON::Unsupported => ()
+            }
+        }
+
+        errors.into_result(Tilemap {
+            tile_size: [map.tilewidth, map.tileheight],
+            layers,
+            tilesets,
+            objects,
+            objects_spawned: false
+        })
+    }
+}
+
+impl TileObject {
+    // An optional "overrides" string property holds a JSON array of component overrides for the prefab.
+    fn from_tiled(object: TiledObjectJSON, source: &JSONSource) -> Result<Self> {
+        if object.r#type.is_empty() {
+            return Err(source.join("/type").locate(anyhow::Error::new(MissingObjectTypeError {
+                object: object.name
+            })))
+        }
+
+        let mut overrides = Vec::new();
+
+        for (index, property) in object.properties.iter().enumerate() {
+            if property.name != "overrides" {
+                continue;
+            }
+
+            let property_source = source.join(format!("/properties/{}/value", index).as_str());
+            let patches: Value = match &property.value {
+                Value::String(text) => serde_json::from_str(text.as_str())
+                    .map_err(|e| property_source.locate(anyhow::Error::new(ObjectPropertyError {
+                        object: object.name.clone(),
+                        source: e
+                    })))?,
+                value => value.clone()
+            };
+
+            let patches: Vec<ComponentJSON> = convert_json(&patches, stringify!(Vec<ComponentJSON>))
+                .map_err(|e| property_source.locate(anyhow::Error::new(e)))?;
+            overrides.extend(patches.into_iter().map(|patch| patch.with_source(property_source.clone())));
+        }
+
+        Ok(TileObject {
+            prefab_id: object.r#type,
+            position: [object.x, object.y],
+            overrides
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct TilemapLoader {
+    tilemap_json: TilemapJSON
+}
+
+impl ComponentLoader for TilemapLoader {
+    fn from_json(json: JSONLoad) -> Result<Self> where Self: Sized {
+        if json.load_type_id == TILEMAP_LOAD_ID {
+            Ok(TilemapLoader {
+                tilemap_json: convert_json(&json.actual_value, stringify!(TilemapJSON))?
+            })
+        } else {
+            Err(anyhow::Error::new(
+                LoadIDMatchError {
+                    expected_id: TILEMAP_LOAD_ID.to_string(),
+                    actual_id: json.load_type_id
+                }
+            ))
+        }
+    }
+
+    fn load_component<'a>(&self, builder: LazyBuilder<'a>, ecs: &World, _window: &Window) -> Result<LazyBuilder<'a>> {
+        self.build_component(builder, ecs)
+    }
+
+    fn set_value(&mut self, new_value: JSONLoad) -> Result<()> {
+        if new_value.load_type_id == TILEMAP_LOAD_ID {
+            self.tilemap_json = convert_json(&new_value.actual_value, stringify!(TilemapJSON))?;
+
+            Ok(())
+        } else {
+            Err(anyhow::Error::new(
+                LoadIDMatchError {
+                    expected_id: TILEMAP_LOAD_ID.to_string(),
+                    actual_id: new_value.load_type_id
+                }
+            ))
+        }
+    }
+
+    fn get_component_name(&self) -> String {
+        TILEMAP_LOAD_ID.to_string()
+    }
+}
+
+impl BuildComponent for TilemapLoader {
+    fn build_component<'a>(&self, builder: LazyBuilder<'a>, _ecs: &World) -> Result<LazyBuilder<'a>> {
+        Ok(builder.with(Tilemap::load(self.tilemap_json.map.as_str())?))
+    }
+
+    fn into_loader(self: Box<Self>) -> Box<dyn ComponentLoader> {
+        self
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum TilemapError {
+    #[error("{file_path} is a TMX map; export it from Tiled as JSON instead")]
+    UnsupportedMapFormatError {
+        file_path: String
+    },
+    #[error("Tile layer {layer} is {width}x{height} tiles but has {actual}")]
+    TileLayerSizeError {
+        layer: String,
+        width: u32,
+        height: u32,
+        actual: usize
+    },
+    #[error("Object {object:?} has an overrides property that isn't valid JSON")]
+    ObjectPropertyError {
+        object: String,
+        source: serde_json::Error
+    },
+    #[error("Object {object:?} has no type naming the prefab to spawn")]
+    MissingObjectTypeError {
+        object: String
+    },
+    #[error("Tileset {tileset:?} has no image of the same name in the scene's assets.images")]
+    UndeclaredTilesetError {
+        tileset: String
+    },
+    #[error("Tileset {tileset:?} is {width}x{height}px, larger than the {limit}px a tile source can address")]
+    TilesetSizeError {
+        tileset: String,
+        width: u32,
+        height: u32,
+        limit: u32
+    }
+}
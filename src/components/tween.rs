@@ -0,0 +1,142 @@
+use specs::{Component, DenseVecStorage};
+use crate::components::BasicTestComponent;
+use crate::easing::Easing;
+use serde::{Deserialize, Serialize};
+
+pub const TWEEN_LOAD_ID: &str = "tween";
+
+// What a step animates, with its start and end values. `mesh_bounds` are [x, y, width, height].
+#[derive(Deserialize, Serialize, Debug, Copy, Clone, PartialEq)]
+#[serde(tag = "property", rename_all = "snake_case")]
+pub enum TweenProperty {
+    Position {
+        from: [f32; 2],
+        to: [f32; 2]
+    },
+    TextColor {
+        from: [f32; 4],
+        to: [f32; 4]
+    },
+    TextSize {
+        from: f32,
+        to: f32
+    },
+    MeshBounds {
+        from: [f32; 4],
+        to: [f32; 4]
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Copy, Clone, PartialEq)]
+pub struct TweenStep {
+    #[serde(flatten)]
+    pub property: TweenProperty,
+    pub duration: f32,
+    #[serde(default)]
+    pub delay: f32,
+    #[serde(default)]
+    pub easing: Easing
+}
+
+// Plays `steps` one after another. With `yoyo` the sequence then plays back in reverse, and with
+// `repeat` the whole thing starts over instead of finishing.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Tween {
+    pub steps: Vec<TweenStep>,
+    #[serde(default)]
+    pub yoyo: bool,
+    #[serde(default)]
+    pub repeat: bool,
+    #[serde(skip)]
+    state: TweenState
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+struct TweenState {
+    step: usize,
+    elapsed: f32,
+    reversed: bool,
+    finished: bool
+}
+
+impl Tween {
+    pub fn is_finished(&self) -> bool {
+        self.state.finished || self.steps.is_empty()
+    }
+
+    // Moves the tween on by `dt` and returns each step it touched with that step's eased progress.
+    // A step finished during this tick is included at its end value, so it never stops short.
+    pub fn advance(&mut self, dt: f32) -> Vec<(TweenProperty, f32)> {
+        let mut samples = Vec::new();
+        if self.is_finished() {
+            return samples;
+        }
+
+        let mut remaining = dt;
+        // Zero-length steps would otherwise spin forever when repeating.
+        let mut steps_left = self.steps.len() * 2 + 1;
+
+        while !self.state.finished && steps_left > 0 {
+            let step = self.steps[self.state.step];
+            let length = step.delay + step.duration;
+
+            if self.state.elapsed + remaining < length {
+                self.state.elapsed += remaining;
+                if self.state.elapsed >= step.delay {
+                    samples.push((step.property, self.progress(&step)));
+                }
+                break;
+            }
+
+            remaining -= length - self.state.elapsed;
+            self.state.elapsed = length;
+            samples.push((step.property, self.progress(&step)));
+
+            self.next_step();
+            steps_left -= 1;
+        }
+
+        samples
+    }
+
+    fn progress(&self, step: &TweenStep) -> f32 {
+        let linear = if step.duration > 0.0 {
+            ((self.state.elapsed - step.delay) / step.duration).max(0.0).min(1.0)
+        } else {
+            1.0
+        };
+
+        step.easing.apply(if self.state.reversed { 1.0 - linear } else { linear })
+    }
+
+    fn next_step(&mut self) {
+        let state = &mut self.state;
+        state.elapsed = 0.0;
+
+        if !state.reversed {
+            if state.step + 1 < self.steps.len() {
+                state.step += 1;
+            } else if self.yoyo {
+                state.reversed = true;
+            } else if self.repeat {
+                state.step = 0;
+            } else {
+                state.finished = true;
+            }
+        } else if state.step > 0 {
+            state.step -= 1;
+        } else if self.repeat {
+            state.reversed = false;
+        } else {
+            state.finished = true;
+        }
+    }
+}
+
+impl Component for Tween {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl BasicTestComponent for Tween {
+    const LOAD_ID: &'static str = TWEEN_LOAD_ID;
+}
@@ -0,0 +1,20 @@
+use specs::{Component, VecStorage};
+use crate::components::BasicTestComponent;
+use serde::{Deserialize, Serialize};
+
+pub const VELOCITY_LOAD_ID: &str = "velocity";
+
+// Pixels per second, applied to Position by the integrate_physics system.
+#[derive(Deserialize, Serialize, Debug, Copy, Clone, Default, PartialEq)]
+pub struct Velocity {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Component for Velocity {
+    type Storage = VecStorage<Self>;
+}
+
+impl BasicTestComponent for Velocity {
+    const LOAD_ID: &'static str = VELOCITY_LOAD_ID;
+}
@@ -0,0 +1,39 @@
+use specs::World;
+
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, PoisonError};
+
+// Scoped access to the shared world. A poisoned lock only means some other holder panicked, and
+// the world is still consistent between specs calls, so the guard is recovered instead of erroring.
+pub trait WorldAccess {
+    fn read_world(&self) -> RwLockReadGuard<'_, World>;
+
+    fn write_world(&self) -> RwLockWriteGuard<'_, World>;
+
+    fn with_world<R, F: FnOnce(&World) -> R>(&self, f: F) -> R {
+        f(&*self.read_world())
+    }
+
+    fn with_world_mut<R, F: FnOnce(&mut World) -> R>(&self, f: F) -> R {
+        f(&mut *self.write_world())
+    }
+}
+
+impl WorldAccess for RwLock<World> {
+    fn read_world(&self) -> RwLockReadGuard<'_, World> {
+        self.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write_world(&self) -> RwLockWriteGuard<'_, World> {
+        self.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl WorldAccess for Arc<RwLock<World>> {
+    fn read_world(&self) -> RwLockReadGuard<'_, World> {
+        (**self).read_world()
+    }
+
+    fn write_world(&self) -> RwLockWriteGuard<'_, World> {
+        (**self).write_world()
+    }
+}
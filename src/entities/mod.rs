@@ -0,0 +1,413 @@
+pub mod spawn_queue;
+
+use specs::{World, WorldExt, Entity, LazyUpdate, Builder};
+use specs::world::LazyBuilder;
+
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use anyhow::Result;
+use thiserror::Error;
+
+use tracing::debug;
+
+use game_engine::load::JSONLoad;
+
+use crate::components::BasicTestComponentMux;
+use crate::components::position::{Position, POSITION_LOAD_ID};
+use crate::components::local_offset::{LocalOffset, LOCAL_OFFSET_LOAD_ID};
+use crate::components::parent::Parent;
+use crate::globals::{load_json, merge_json, convert_json, JSONSource, LoadErrors};
+use crate::globals::TestGlobalError::LoadIDMatchError;
+use crate::entities::EntityError::{PrefabCycleError, OverrideTargetError};
+
+pub const ENTITY_LOAD_ID: &str = "entity_loader";
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ComponentJSON {
+    pub load_type_id: String,
+    pub actual_value: Value,
+    #[serde(skip)]
+    pub source: JSONSource
+}
+
+impl ComponentJSON {
+    pub fn new(load_type_id: &str, actual_value: Value) -> Self {
+        ComponentJSON {
+            load_type_id: load_type_id.to_string(),
+            actual_value,
+            source: JSONSource::default()
+        }
+    }
+
+    pub fn with_source(mut self, source: JSONSource) -> Self {
+        self.source = source;
+        self
+    }
+}
+
+impl From<ComponentJSON> for JSONLoad {
+    fn from(json: ComponentJSON) -> Self {
+        JSONLoad {
+            load_type_id: json.load_type_id,
+            actual_value: json.actual_value
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct EntityJSON {
+    #[serde(default)]
+    base: Option<String>,
+    #[serde(default)]
+    component_paths: Vec<String>,
+    #[serde(default)]
+    components: Vec<ComponentJSON>,
+    #[serde(default)]
+    overrides: Vec<ComponentJSON>,
+    #[serde(default)]
+    children: Vec<EntityJSON>
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EntityInstanceJSON {
+    pub entity: String,
+    #[serde(default = "default_instance_count")]
+    pub count: usize,
+    #[serde(default)]
+    pub position: Option<[f32; 2]>,
+    #[serde(default)]
+    pub spacing: [f32; 2],
+    #[serde(default)]
+    pub overrides: Vec<ComponentJSON>,
+    #[serde(default)]
+    pub instances: Vec<InstanceJSON>,
+    #[serde(skip)]
+    pub source: JSONSource
+}
+
+// Per-index settings for one copy of an EntityInstanceJSON, applied on top of the shared ones.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct InstanceJSON {
+    #[serde(default)]
+    pub position: Option<[f32; 2]>,
+    #[serde(default)]
+    pub overrides: Vec<ComponentJSON>
+}
+
+fn default_instance_count() -> usize {
+    1
+}
+
+impl EntityInstanceJSON {
+    pub fn build_entities(&self, ecs: &World) -> Result<Vec<Entity>> {
+        let prefab = EntityLoader::from_file(self.entity.as_str())
+            .map_err(|e| self.source.join("/entity").locate(e))?;
+        let count = self.count.max(self.instances.len());
+        let default_instance = InstanceJSON::default();
+
+        let mut errors = LoadErrors::default();
+        let mut entities = Vec::with_capacity(count);
+
+        for index in 0..count {
+            let instance = self.instances.get(index).unwrap_or(&default_instance);
+            let mut loader = prefab.clone();
+
+            let shared_overrides = self.overrides.iter().enumerate()
+                .map(|(i, patch)| (format!("/overrides/{}", i), patch));
+            let instance_overrides = instance.overrides.iter().enumerate()
+                .map(|(i, patch)| (format!("/instances/{}/overrides/{}", index, i), patch));
+
+            for (pointer, patch) in shared_overrides.chain(instance_overrides) {
+                let patch = patch.clone().with_source(self.source.join(pointer.as_str()));
+                errors.collect(loader.apply_override(patch));
+            }
+
+            // The rest of the overrides are still checked so every bad one is reported, but nothing
+            // more is built once the instance can't load as a whole.
+            if !errors.is_empty() {
+                continue;
+            }
+
+            let position = instance.position.or_else(|| {
+                self.position.map(|[x, y]| {
+                    [x + self.spacing[0] * index as f32, y + self.spacing[1] * index as f32]
+                })
+            });
+
+            if let Some([x, y]) = position {
+                loader.insert_component(
+                    ComponentJSON::new(POSITION_LOAD_ID, json!({ "x": x, "y": y }))
+                        .with_source(self.source.join("/position"))
+                );
+            }
+
+            if let Some(built) = errors.collect(loader.build_entity(ecs)) {
+                entities.extend(built);
+            }
+        }
+
+        if !errors.is_empty() {
+            delete_entities(ecs, &entities);
+        }
+
+        errors.into_result(entities)
+    }
+}
+
+// Children are built as separate entities with a Parent pointing at this one.
+#[derive(Debug, Clone, Default)]
+pub struct EntityLoader {
+    components: Vec<ComponentJSON>,
+    children: Vec<EntityLoader>
+}
+
+impl EntityLoader {
+    pub fn from_file(file_path: &str) -> Result<Self> {
+        Self::resolve_file(file_path, &mut Vec::new())
+    }
+
+    fn resolve_file(file_path: &str, visited: &mut Vec<String>) -> Result<Self> {
+        if visited.iter().any(|path| path == file_path) {
+            return Err(anyhow::Error::new(PrefabCycleError {
+                file_path: file_path.to_string(),
+                chain: visited.clone()
+            }))
+        }
+        visited.push(file_path.to_string());
+        debug!(file_path, depth = visited.len(), "Resolving entity file");
+
+        let loader = load_json::<ComponentJSON>(file_path)
+            .and_then(|json| Self::resolve_json(json, file_path, visited));
+
+        visited.pop();
+        loader
+    }
+
+    fn resolve_json(json: ComponentJSON, file_path: &str, visited: &mut Vec<String>) -> Result<Self> {
+        let source = JSONSource::new(file_path, "/actual_value");
+
+        if json.load_type_id != ENTITY_LOAD_ID {
+            return Err(JSONSource::new(file_path, "/load_type_id").locate(anyhow::Error::new(
+                LoadIDMatchError {
+                    expected_id: ENTITY_LOAD_ID.to_string(),
+                    actual_id: json.load_type_id
+                }
+            )))
+        }
+
+        let entity_json: EntityJSON = convert_json(&json.actual_value, stringify!(EntityJSON))
+            .map_err(|e| source.locate(anyhow::Error::new(e)))?;
+
+        Self::resolve_entity(entity_json, &source, visited)
+    }
+
+    fn resolve_entity(entity_json: EntityJSON, source: &JSONSource, visited: &mut Vec<String>) -> Result<Self> {
+        let mut errors = LoadErrors::default();
+
+        let mut loader = match &entity_json.base {
+            Some(base_path) => errors.collect(
+                Self::resolve_file(base_path, visited)
+                    .map_err(|e| source.join("/base").locate(e))
+            ).unwrap_or_default(),
+            None => Self::default()
+        };
+
+        for component_path in &entity_json.component_paths {
+            let component = load_json::<ComponentJSON>(component_path)
+                .map(|component| component.with_source(JSONSource::new(component_path, "/actual_value")));
+
+            if let Some(component) = errors.collect(component) {
+                loader.insert_component(component);
+            }
+        }
+
+        for (index, component) in entity_json.components.into_iter().enumerate() {
+            let pointer = format!("/components/{}/actual_value", index);
+            loader.insert_component(component.with_source(source.join(pointer.as_str())));
+        }
+
+        for (index, patch) in entity_json.overrides.into_iter().enumerate() {
+            let pointer = format!("/overrides/{}", index);
+            errors.collect(loader.apply_override(patch.with_source(source.join(pointer.as_str()))));
+        }
+
+        // Inline children add to any the base prefab already has.
+        for (index, child_json) in entity_json.children.into_iter().enumerate() {
+            let child_source = source.join(format!("/children/{}", index).as_str());
+            if let Some(child) = errors.collect(Self::resolve_entity(child_json, &child_source, visited)) {
+                loader.children.push(child);
+            }
+        }
+
+        errors.into_result(loader)
+    }
+
+    // A component with the same load_type_id as an inherited one replaces it outright.
+    pub fn insert_component(&mut self, component: ComponentJSON) {
+        match self.components.iter_mut().find(|c| c.load_type_id == component.load_type_id) {
+            Some(existing) => *existing = component,
+            None => self.components.push(component)
+        }
+    }
+
+    pub fn apply_override(&mut self, patch: ComponentJSON) -> Result<()> {
+        let target = self.components.iter_mut()
+            .find(|c| c.load_type_id == patch.load_type_id)
+            .ok_or_else(|| {
+                patch.source.locate(anyhow::Error::new(OverrideTargetError {
+                    load_type_id: patch.load_type_id.clone()
+                }))
+            })?;
+
+        merge_json(&mut target.actual_value, &patch.actual_value);
+
+        Ok(())
+    }
+
+    // A bad value is left for the component's own loader to report.
+    fn component_value<T: DeserializeOwned>(&self, load_type_id: &str) -> Option<T> {
+        self.components.iter()
+            .find(|component| component.load_type_id == load_type_id)
+            .and_then(|component| serde_json::from_value(component.actual_value.clone()).ok())
+    }
+
+    // Returns the entity followed by all of its descendants, so callers can track and delete them together.
+    pub fn build_entity(&self, ecs: &World) -> Result<Vec<Entity>> {
+        self.build_tree(ecs, None)
+    }
+
+    // Components are only inserted once the world maintains, so each parent's Position is handed down
+    // from its JSON. Children with a LocalOffset start where propagate_transforms would put them,
+    // instead of at their own Position or nowhere until the first tick.
+    fn build_tree(&self, ecs: &World, parent: Option<(Entity, Option<Position>)>) -> Result<Vec<Entity>> {
+        let parent_position = parent.and_then(|(_, position)| position);
+        let offset = self.component_value::<LocalOffset>(LOCAL_OFFSET_LOAD_ID);
+        let offset_position = match (parent_position, offset) {
+            (Some(parent_position), Some(offset)) => Some(Position {
+                x: parent_position.x + offset.x,
+                y: parent_position.y + offset.y
+            }),
+            _ => None
+        };
+        let position = offset_position.or_else(|| self.component_value::<Position>(POSITION_LOAD_ID));
+
+        let entity = self.build_single(ecs, parent.map(|(entity, _)| entity), offset_position)?;
+        let mut entities = vec![entity];
+
+        let mut errors = LoadErrors::default();
+        for child in &self.children {
+            if let Some(descendants) = errors.collect(child.build_tree(ecs, Some((entity, position)))) {
+                entities.extend(descendants);
+            }
+        }
+
+        if !errors.is_empty() {
+            delete_entities(ecs, &entities);
+        }
+
+        errors.into_result(entities)
+    }
+
+    // Tries every component so one bad file doesn't hide the others; the entity is deleted if any fail.
+    fn build_single(&self, ecs: &World, parent: Option<Entity>, offset_position: Option<Position>) -> Result<Entity> {
+        let lazy_update = ecs.read_resource::<LazyUpdate>();
+        let mut builder = lazy_update.create_entity(&ecs.entities());
+        let entity = builder.entity;
+
+        let mut errors = LoadErrors::default();
+
+        for component in &self.components {
+            let result = BasicTestComponentMux::map_json_to_builder(component.clone().into())
+                .and_then(|loader| loader.build_component(builder, ecs));
+
+            builder = match result {
+                Ok(builder) => builder,
+                Err(e) => {
+                    errors.push(component.source.locate(e));
+
+                    LazyBuilder {
+                        entity,
+                        lazy: &*lazy_update
+                    }
+                }
+            };
+        }
+
+        if !errors.is_empty() {
+            let _ = ecs.entities().delete(entity);
+            return errors.into_result(entity)
+        }
+
+        if let Some(parent) = parent {
+            builder = builder.with(Parent { entity: parent });
+        }
+
+        if let Some(position) = offset_position {
+            builder = builder.with(position);
+        }
+
+        let entity = builder.build();
+        debug!(entity = entity.id(), components = self.components.len(), children = self.children.len(), "Built entity");
+
+        Ok(entity)
+    }
+}
+
+// Deletes entities built before a later error, so a failed load leaves nothing behind in the world.
+fn delete_entities(ecs: &World, entities: &[Entity]) {
+    for entity in entities {
+        let _ = ecs.entities().delete(*entity);
+    }
+}
+
+pub fn load_entity_vec(entity_paths: &Vec<String>, ecs: &World) -> Result<Vec<Entity>> {
+    let mut errors = LoadErrors::default();
+
+    let entities: Vec<Entity> = entity_paths.iter()
+        .filter_map(|entity_path| {
+            errors.collect(
+                EntityLoader::from_file(entity_path)
+                    .and_then(|loader| loader.build_entity(ecs))
+            )
+        })
+        .flatten()
+        .collect();
+
+    if !errors.is_empty() {
+        delete_entities(ecs, &entities);
+    }
+
+    errors.into_result(entities)
+}
+
+pub fn load_entity_instances(instances: &Vec<EntityInstanceJSON>, ecs: &World) -> Result<Vec<Entity>> {
+    let mut errors = LoadErrors::default();
+    let mut entities = Vec::new();
+
+    for instance in instances {
+        if let Some(instance_entities) = errors.collect(instance.build_entities(ecs)) {
+            entities.extend(instance_entities);
+        }
+    }
+
+    if !errors.is_empty() {
+        delete_entities(ecs, &entities);
+    }
+
+    errors.into_result(entities)
+}
+
+#[derive(Error, Debug)]
+pub enum EntityError {
+    #[error("Prefab {file_path} inherits from itself through: {chain:?}")]
+    PrefabCycleError {
+        file_path: String,
+        chain: Vec<String>
+    },
+    #[error("Override for {load_type_id} has no matching component to patch")]
+    OverrideTargetError {
+        load_type_id: String
+    }
+}
@@ -0,0 +1,144 @@
+use specs::{World, WorldExt, Entity};
+
+use serde_json::json;
+
+use anyhow::Result;
+
+use tracing::error;
+
+use std::collections::HashMap;
+
+use crate::entities::{ComponentJSON, EntityLoader};
+use crate::components::position::POSITION_LOAD_ID;
+use crate::components::parent::with_descendants;
+use crate::globals::{LOAD_PATH, JSON_ASSETS_DIR, JSON_FILE};
+
+pub const ENTITIES_DIR: &str = "entities/";
+
+#[derive(Debug, Clone)]
+pub struct SpawnRequest {
+    pub prefab_id: String,
+    pub position: Option<[f32; 2]>,
+    pub overrides: Vec<ComponentJSON>
+}
+
+impl SpawnRequest {
+    pub fn new(prefab_id: &str) -> Self {
+        SpawnRequest {
+            prefab_id: prefab_id.to_string(),
+            position: None,
+            overrides: Vec::new()
+        }
+    }
+
+    pub fn with_position(mut self, x: f32, y: f32) -> Self {
+        self.position = Some([x, y]);
+        self
+    }
+
+    pub fn with_override(mut self, patch: ComponentJSON) -> Self {
+        self.overrides.push(patch);
+        self
+    }
+}
+
+// Resource that systems push into. Scenes drain both after each update's ticks.
+#[derive(Debug, Default)]
+pub struct SpawnQueue {
+    spawns: Vec<SpawnRequest>,
+    despawns: Vec<Entity>
+}
+
+impl SpawnQueue {
+    pub fn spawn(&mut self, request: SpawnRequest) {
+        self.spawns.push(request);
+    }
+
+    pub fn despawn(&mut self, entity: Entity) {
+        self.despawns.push(entity);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spawns.is_empty() && self.despawns.is_empty()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct EntitySpawner {
+    prefabs: HashMap<String, EntityLoader>
+}
+
+impl EntitySpawner {
+    pub fn prefab_path(prefab_id: &str) -> String {
+        if prefab_id.ends_with(JSON_FILE) {
+            prefab_id.to_string()
+        } else {
+            [
+                LOAD_PATH,
+                JSON_ASSETS_DIR,
+                ENTITIES_DIR,
+                prefab_id,
+                JSON_FILE
+            ].join("")
+        }
+    }
+
+    fn get_prefab(&mut self, prefab_id: &str) -> Result<&EntityLoader> {
+        if !self.prefabs.contains_key(prefab_id) {
+            let loader = EntityLoader::from_file(Self::prefab_path(prefab_id).as_str())?;
+            self.prefabs.insert(prefab_id.to_string(), loader);
+        }
+
+        Ok(&self.prefabs[prefab_id])
+    }
+
+    fn build_request(&mut self, request: SpawnRequest, ecs: &World) -> Result<Vec<Entity>> {
+        let mut loader = self.get_prefab(request.prefab_id.as_str())?.clone();
+
+        for patch in request.overrides {
+            loader.apply_override(patch)?;
+        }
+
+        if let Some([x, y]) = request.position {
+            loader.insert_component(ComponentJSON::new(POSITION_LOAD_ID, json!({ "x": x, "y": y })));
+        }
+
+        loader.build_entity(ecs)
+    }
+
+    // Builds every queued spawn and returns the new entities. A request that fails to build is
+    // logged and skipped so it can't block the rest of the queue.
+    pub fn drain_spawns(&mut self, ecs: &mut World) -> Vec<Entity> {
+        let spawns = std::mem::take(&mut ecs.fetch_mut::<SpawnQueue>().spawns);
+
+        let mut spawned = Vec::with_capacity(spawns.len());
+        for request in spawns {
+            let prefab_id = request.prefab_id.clone();
+
+            match self.build_request(request, ecs) {
+                Ok(entities) => spawned.extend(entities),
+                Err(e) => error!(prefab_id = %prefab_id, "Failed to spawn entity: {:#}", e)
+            }
+        }
+
+        ecs.maintain();
+
+        spawned
+    }
+
+    // Deletes every queued despawn along with its children and returns the entities removed.
+    pub fn drain_despawns(ecs: &mut World) -> Result<Vec<Entity>> {
+        let despawns = std::mem::take(&mut ecs.fetch_mut::<SpawnQueue>().despawns);
+
+        let mut despawned: Vec<Entity> = with_descendants(ecs, despawns).into_iter()
+            .filter(|entity| ecs.is_alive(*entity))
+            .collect();
+        despawned.sort();
+        despawned.dedup();
+        ecs.delete_entities(despawned.as_slice())?;
+
+        ecs.maintain();
+
+        Ok(despawned)
+    }
+}
@@ -0,0 +1,33 @@
+use specs::Entity;
+use specs::shrev::EventChannel;
+
+use crate::input::{Action, ActionState};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    Collision {
+        first: Entity,
+        second: Entity,
+        trigger: bool
+    },
+    EntitySpawned {
+        entity: Entity
+    },
+    EntityDespawned {
+        entity: Entity
+    },
+    ActionTriggered {
+        action: Action,
+        state: ActionState
+    },
+    SceneChanged {
+        scene_name: String
+    },
+    WindowResized {
+        width: f32,
+        height: f32
+    }
+}
+
+// Each system that reads this registers its own ReaderId in setup, so readers never steal each other's events.
+pub type GameEvents = EventChannel<GameEvent>;
@@ -1,103 +1,171 @@
 use std::sync::{Arc, RwLock};
 use crate::input::TestCustomInput;
-use crate::globals::{JSON_FILE, LOAD_PATH, JSON_ASSETS_DIR, FONTS_DIR};
-use crate::scenes::{scene_factory, SCENE_STACKS_DIR};
+use crate::globals::{JSON_FILE, LOAD_PATH, JSON_ASSETS_DIR, FONTS_DIR, LoadErrors, TempJSON};
+use crate::scenes::{scene_factory, register_scene_stack, SCENE_STACKS_DIR};
 use game_engine::game::GameWrapper;
 use game_engine::scenes::scene_stack::{SceneStack, SceneStackLoader};
 use game_engine::scenes::SCENES_DIR;
 use crate::scenes::basic_test_scene::BASIC_TEST_SCENE_FILE_ID;
+use crate::scenes::load_error_scene::error_scene_stack;
+use crate::loading_screen::stage;
 use coffee::graphics::Window;
-use coffee::load::{Task, Join};
+use coffee::load::Task;
 use specs::{World, WorldExt};
-use crate::components::basic_boolean_test::BasicBooleanTest;
-use crate::components::basic_number_test::BasicNumberTest;
-use crate::components::basic_text_test::BasicTextTest;
-use crate::components::basic_vec_test::BasicVectorTest;
-use crate::components::basic_map_test::BasicMapTest;
+use crate::components::basic_boolean_test::{BasicBooleanTest, BASIC_BOOLEAN_TEST_COMPONENT_LOAD_ID};
+use crate::components::basic_number_test::{BasicNumberTest, BASIC_NUMBER_TEST_COMPONENT_LOAD_ID};
+use crate::components::basic_text_test::{BasicTextTest, BASIC_TEXT_TEST_COMPONENT_LOAD_ID};
+use crate::components::basic_vec_test::{BasicVectorTest, BASIC_VECTOR_TEST_COMPONENT_LOAD_ID};
+use crate::components::basic_map_test::{BasicMapTest, BASIC_MAP_TEST_COMPONENT_LOAD_ID};
 use crate::components::mesh_graphic::MeshGraphic;
+use crate::components::drawable::Drawable;
 use crate::components::text_display::TextDisplay;
-use crate::globals::TestGlobalError::ECSWriteError;
-use game_engine::components::drawables::Drawable;
-use kira::manager::AudioManagerSettings;
-use game_engine::components::audibles::default_sound::DefaultSound;
-use game_engine::globals::font_dict::{FontDictLoader, FONT_DICT_LOAD_ID};
-use game_engine::globals::image_dict::{ImageDictLoader, IMAGE_DICT_LOAD_ID};
-use game_engine::globals::audio_controller::{AudioControllerLoader, AUDIO_CONTROLLER_LOAD_ID};
+use crate::components::position::{Position, POSITION_LOAD_ID};
+use crate::components::registry::ComponentRegistry;
+use crate::components::player_control::{PlayerControl, PLAYER_CONTROL_LOAD_ID};
+use crate::components::screen_space::{ScreenSpace, SCREEN_SPACE_LOAD_ID};
+use crate::components::anchor::{Anchor, ANCHOR_LOAD_ID};
+use crate::components::sprite::{AtlasSprite, ATLAS_SPRITE_LOAD_ID};
+use crate::components::animation::{Animation, ANIMATION_LOAD_ID};
+use crate::components::tilemap::Tilemap;
+use crate::components::collider::{Collider, COLLIDER_LOAD_ID};
+use crate::components::velocity::{Velocity, VELOCITY_LOAD_ID};
+use crate::components::rigid_body::{RigidBody, RIGID_BODY_LOAD_ID};
+use crate::components::particle_emitter::{ParticleEmitter, PARTICLE_EMITTER_LOAD_ID};
+use crate::components::tween::{Tween, TWEEN_LOAD_ID};
+use crate::components::render_layer::{RenderLayer, RENDER_LAYER_LOAD_ID};
+use crate::components::local_offset::{LocalOffset, LOCAL_OFFSET_LOAD_ID};
+use crate::components::default_sound::{DefaultSound, DEFAULT_SOUND_LOAD_ID};
+use crate::components::parent::Parent;
+use crate::components::previous_position::PreviousPosition;
+use crate::entities::spawn_queue::SpawnQueue;
+use tracing::{info, error};
+use crate::timestep::{DeltaTime, Interpolation};
+use crate::rendering::camera::Camera;
+use crate::rendering::debug_draw::DebugDraw;
+use crate::settings::{self, Settings};
+use crate::rendering::viewport::Viewport;
+use crate::cli::{self, single_scene_stack};
+use crate::random::GameRng;
+use crate::audio::AudioPlayer;
+use crate::ecs_access::WorldAccess;
 
 pub struct BasicTestGameWrapper {}
 
 impl GameWrapper<TestCustomInput> for BasicTestGameWrapper {
     fn register_components(ecs: &mut World) {
-        ecs.register::<BasicBooleanTest>();
-        ecs.register::<BasicNumberTest>();
-        ecs.register::<BasicTextTest>();
-        ecs.register::<BasicVectorTest>();
-        ecs.register::<BasicMapTest>();
-        ecs.register::<Drawable>();
-        ecs.register::<DefaultSound>();
+        let mut registry = ComponentRegistry::default();
+
+        registry.register_editable::<BasicBooleanTest>(ecs, "BasicBooleanTest", BASIC_BOOLEAN_TEST_COMPONENT_LOAD_ID);
+        registry.register_editable::<BasicNumberTest>(ecs, "BasicNumberTest", BASIC_NUMBER_TEST_COMPONENT_LOAD_ID);
+        registry.register_editable::<BasicTextTest>(ecs, "BasicTextTest", BASIC_TEXT_TEST_COMPONENT_LOAD_ID);
+        registry.register_editable::<BasicVectorTest>(ecs, "BasicVectorTest", BASIC_VECTOR_TEST_COMPONENT_LOAD_ID);
+        registry.register_editable::<BasicMapTest>(ecs, "BasicMapTest", BASIC_MAP_TEST_COMPONENT_LOAD_ID);
+        registry.register_editable::<Position>(ecs, "Position", POSITION_LOAD_ID);
+        registry.register_editable::<PlayerControl>(ecs, "PlayerControl", PLAYER_CONTROL_LOAD_ID);
+        registry.register_editable::<ScreenSpace>(ecs, "ScreenSpace", SCREEN_SPACE_LOAD_ID);
+        registry.register_editable::<Anchor>(ecs, "Anchor", ANCHOR_LOAD_ID);
+        registry.register_editable::<AtlasSprite>(ecs, "AtlasSprite", ATLAS_SPRITE_LOAD_ID);
+        registry.register_editable::<Animation>(ecs, "Animation", ANIMATION_LOAD_ID);
+        registry.register_editable::<Collider>(ecs, "Collider", COLLIDER_LOAD_ID);
+        registry.register_editable::<Velocity>(ecs, "Velocity", VELOCITY_LOAD_ID);
+        registry.register_editable::<RigidBody>(ecs, "RigidBody", RIGID_BODY_LOAD_ID);
+        registry.register_editable::<ParticleEmitter>(ecs, "ParticleEmitter", PARTICLE_EMITTER_LOAD_ID);
+        registry.register_editable::<Tween>(ecs, "Tween", TWEEN_LOAD_ID);
+        registry.register_editable::<RenderLayer>(ecs, "RenderLayer", RENDER_LAYER_LOAD_ID);
+        registry.register_editable::<LocalOffset>(ecs, "LocalOffset", LOCAL_OFFSET_LOAD_ID);
+        registry.register_editable::<DefaultSound>(ecs, "DefaultSound", DEFAULT_SOUND_LOAD_ID);
+        registry.register::<MeshGraphic>(ecs, "MeshGraphic");
+        registry.register::<TextDisplay>(ecs, "TextDisplay");
+        registry.register::<Tilemap>(ecs, "Tilemap");
+        registry.register::<Parent>(ecs, "Parent");
+        registry.register::<PreviousPosition>(ecs, "PreviousPosition");
+        registry.register::<Drawable>(ecs, "Drawable");
+
+        ecs.insert(registry);
     }
 
     fn load(window: &Window) -> Task<(Arc<RwLock<World>>, SceneStack<TestCustomInput>)> {
         let ecs = Arc::new(RwLock::new(World::new()));
 
-        let font_dict_loader = FontDictLoader::new(
-            [
-                LOAD_PATH,
-                JSON_ASSETS_DIR,
-                FONT_DICT_LOAD_ID,
-                JSON_FILE
-            ].join("")
-        );
-
-        let image_dict_loader = ImageDictLoader::new(
-            [
-                LOAD_PATH,
-                JSON_ASSETS_DIR,
-                IMAGE_DICT_LOAD_ID,
-                JSON_FILE
-            ].join("")
-        );
-
-        let audio_controller_loader = AudioControllerLoader::new(
-            [
-                LOAD_PATH,
-                JSON_ASSETS_DIR,
-                AUDIO_CONTROLLER_LOAD_ID,
-                JSON_FILE
-            ].join("")
-        );
-
-        let loader = SceneStackLoader::new(
-            [
-                LOAD_PATH,
-                JSON_ASSETS_DIR,
-                SCENE_STACKS_DIR,
-                "basic_test_scene_stack",
-                JSON_FILE
-            ].join(""),
-            scene_factory
-        );
-
-        (
-            font_dict_loader.load(),
-            image_dict_loader.load(),
-            audio_controller_loader.load(AudioManagerSettings::default()),
-            loader.load(ecs.clone(), window)
-        ).join()
-            .map(|(font_dict, image_dict, audio_controller, scene_stack)| {
-                let mut mut_ecs = ecs.write()
-                    .map_err(|e| {
-                        ECSWriteError {
-                            source_string: e.to_string()
-                        }
-                    }).unwrap();
-
-                mut_ecs.insert(font_dict);
-                mut_ecs.insert(image_dict);
-                mut_ecs.insert(audio_controller);
-                std::mem::drop(mut_ecs); // Manually drop RwLock so we can move ecs in return
+        let settings = settings::current();
+        let mut viewport = settings.display.clone();
+        viewport.resize(window.width(), window.height());
+
+        // A stack the engine can't load would stop the game before anything is drawn, so its errors
+        // are shown by a stack of their own instead.
+        let (scene_stack_path, temp_stack) = starting_scene_stack_path(&settings);
+        let mut temp_files: Vec<TempJSON> = temp_stack.into_iter().collect();
+        let scene_stack_path = match register_scene_stack(scene_stack_path.as_str()) {
+            Ok(_) => scene_stack_path,
+            Err(e) => {
+                let mut errors = LoadErrors::default();
+                errors.push(e);
+
+                match error_scene_stack(scene_stack_path.as_str(), &errors) {
+                    Ok((error_stack, error_scene)) => {
+                        let error_stack_path = error_stack.path();
+                        temp_files.push(error_stack);
+                        temp_files.push(error_scene);
+                        error_stack_path
+                    },
+                    Err(e) => {
+                        error!(scene_stack = %scene_stack_path, "Couldn't write an error scene for the scene stack: {:#}", e);
+                        scene_stack_path
+                    }
+                }
+            }
+        };
+
+        let loader = SceneStackLoader::new(scene_stack_path, scene_factory);
+
+        // Scenes load the fonts, images and sounds they declare themselves, so only the stack loads here.
+        stage("Loading scenes", loader.load(ecs.clone(), window))
+            .map(move |scene_stack| {
+                info!("Loaded scene stack");
+                // Every scene in the stack has been read by now, so temporary stack and scene files can go.
+                drop(temp_files);
+
+                ecs.with_world_mut(|world| {
+                    insert_resources(world, &settings, viewport, AudioPlayer::new(settings.audio));
+                });
 
                 return (ecs, scene_stack)
             })
     }
-}
\ No newline at end of file
+}
+
+// The scene stack --scene or the settings ask for, wrapping a single scene in a temporary stack.
+// That stack's file is deleted once the returned TempJSON is dropped.
+pub fn starting_scene_stack_path(settings: &Settings) -> (String, Option<TempJSON>) {
+    let configured_stack = [
+        LOAD_PATH,
+        JSON_ASSETS_DIR,
+        SCENE_STACKS_DIR,
+        settings.starting_scene_stack.as_str(),
+        JSON_FILE
+    ].join("");
+
+    let temp_stack = cli::options().scene.as_ref().and_then(|scene_path| {
+        single_scene_stack(scene_path.as_str())
+            .map_err(|e| error!(scene = %scene_path, "Couldn't write a scene stack for the scene, starting normally: {:#}", e))
+            .ok()
+    });
+    let scene_stack_path = temp_stack.as_ref().map_or(configured_stack, |stack| stack.path());
+    info!(scene_stack = %scene_stack_path, "Starting scene stack");
+
+    (scene_stack_path, temp_stack)
+}
+
+// Resources every scene expects, shared with --headless, which has no window or audio device.
+pub fn insert_resources(world: &mut World, settings: &Settings, viewport: Viewport, audio_player: AudioPlayer) {
+    world.insert(audio_player);
+    world.insert(SpawnQueue::default());
+    world.insert(DeltaTime::default());
+    world.insert(Interpolation::default());
+    // Scenes may load before this runs and insert their own camera.
+    world.entry::<Camera>().or_insert_with(Camera::default);
+    world.insert(viewport);
+    world.insert(settings.audio);
+    world.insert(GameRng::new(cli::options().seed));
+    world.insert(DebugDraw::new(cli::options().debug_overlays));
+}
@@ -1,15 +1,31 @@
 use game_engine::load::LoadError;
-use serde_json::Value;
+use serde_json::{Value, Map};
+use serde::de::DeserializeOwned;
 use thiserror::Error;
-use std::sync::{PoisonError, RwLockWriteGuard};
-use specs::World;
+use anyhow::Result;
+use std::env::temp_dir;
+use std::fs::{read_to_string, write, remove_file};
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tracing::warn;
+use serde_path_to_error::Segment;
+use crate::globals::TestGlobalError::{FileReadError, ParseJSONError, ConvertJSONError};
 
 pub const LOAD_PATH: &str = "assets/";
 pub const JSON_FILE: &str = ".json";
-pub const JSON_ASSETS_DIR: &str = "json/";
+pub const JSON_ASSETS_DIR: &str = "JSON/";
 pub const FONTS_DIR: &str = "fonts/";
 
 pub const NORMAL_FONT: &str = "astron_boy";
+pub const DEBUG_FONT: &[u8] = include_bytes!("../assets/fonts/astron_boy_video.ttf");
+pub const DEBUG_DIR: &str = "debug/";
+
+// Longest excerpt of the offending JSON that a ConvertJSONError will quote.
+const MAX_ERROR_VALUE_LENGTH: usize = 80;
+
+static TEMP_JSON_COUNTER: AtomicUsize = AtomicUsize::new(0);
 
 #[derive(Error, Debug)]
 pub enum TestGlobalError {
@@ -23,18 +39,236 @@ pub enum TestGlobalError {
         file_path: String,
         source: LoadError
     },
-    #[error("Error converting serde_json::Value: {value} into {into_type}")]
+    #[error("Error converting serde_json::Value at {pointer:?} into {into_type}, found: {value}")]
     ConvertJSONError {
-        value: Value,
+        value: String,
         into_type: String,
+        pointer: String,
         source: serde_json::error::Error
     },
-    #[error("Error getting write lock for ECS")]
-    ECSWriteError {
-        source_string: String
+    #[error("Failed to read file: {file_path}")]
+    FileReadError {
+        file_path: String,
+        source: std::io::Error
     },
-    #[error("Error getting read lock for ECS")]
-    ECSReadError {
-        source_string: String
+    #[error("Failed to parse json in: {file_path}")]
+    ParseJSONError {
+        file_path: String,
+        source: serde_json::error::Error
+    }
+}
+
+// Like serde_json::from_value, but failures carry a JSON pointer to the offending field.
+pub fn convert_json<T: DeserializeOwned>(value: &Value, into_type: &str) -> Result<T, TestGlobalError> {
+    serde_path_to_error::deserialize(value.clone())
+        .map_err(|e| {
+            let pointer = json_pointer(e.path());
+            let found = value.pointer(pointer.as_str()).unwrap_or(value);
+
+            ConvertJSONError {
+                value: truncate_json(found),
+                into_type: into_type.to_string(),
+                pointer,
+                source: e.into_inner()
+            }
+        })
+}
+
+fn truncate_json(value: &Value) -> String {
+    let rendered = value.to_string();
+    match rendered.char_indices().nth(MAX_ERROR_VALUE_LENGTH) {
+        Some((end, _)) => format!("{}...", &rendered[..end]),
+        None => rendered
+    }
+}
+
+fn json_pointer(path: &serde_path_to_error::Path) -> String {
+    path.iter()
+        .filter_map(|segment| match segment {
+            Segment::Seq { index } => Some(format!("/{}", index)),
+            Segment::Map { key } => Some(format!("/{}", key.replace('~', "~0").replace('/', "~1"))),
+            Segment::Enum { variant } => Some(format!("/{}", variant)),
+            Segment::Unknown => None
+        })
+        .collect()
+}
+
+// Where a piece of JSON came from, rendered as e.g. assets/JSON/components/drawable_test.json#/text/0/size
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JSONSource {
+    pub file_path: String,
+    pub pointer: String
+}
+
+impl JSONSource {
+    pub fn new(file_path: &str, pointer: &str) -> Self {
+        JSONSource {
+            file_path: file_path.to_string(),
+            pointer: pointer.to_string()
+        }
+    }
+
+    pub fn join(&self, pointer: &str) -> Self {
+        JSONSource {
+            file_path: self.file_path.clone(),
+            pointer: [self.pointer.as_str(), pointer].join("")
+        }
+    }
+
+    // Attaches this location to an error, extended by the pointer of a nested ConvertJSONError if there is one.
+    pub fn locate(&self, error: anyhow::Error) -> anyhow::Error {
+        let location = match error.downcast_ref::<TestGlobalError>() {
+            Some(ConvertJSONError { pointer, .. }) => self.join(pointer.as_str()),
+            _ => self.clone()
+        };
+
+        error.context(location)
+    }
+}
+
+impl Display for JSONSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}#{}", self.file_path, self.pointer)
+    }
+}
+
+// Every error from one load pass, so a broken scene reports all of its problems at once.
+#[derive(Debug, Default)]
+pub struct LoadErrors {
+    pub errors: Vec<anyhow::Error>
+}
+
+impl LoadErrors {
+    // Nested LoadErrors are flattened, each keeping whatever context (e.g. a JSONSource) was on the batch.
+    pub fn push(&mut self, error: anyhow::Error) {
+        let contexts: Vec<String> = error.chain()
+            .take_while(|cause| !cause.is::<LoadErrors>())
+            .map(|cause| cause.to_string())
+            .collect();
+
+        match error.downcast::<LoadErrors>() {
+            Ok(nested) => {
+                for nested_error in nested.errors {
+                    let located = contexts.iter().rev()
+                        .fold(nested_error, |nested_error, context| nested_error.context(context.clone()));
+                    self.errors.push(located);
+                }
+            },
+            Err(error) => self.errors.push(error)
+        }
+    }
+
+    pub fn collect<T>(&mut self, result: Result<T>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.push(error);
+                None
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn into_result<T>(self, value: T) -> Result<T> {
+        if self.is_empty() {
+            Ok(value)
+        } else {
+            Err(anyhow::Error::new(self))
+        }
+    }
+
+    pub fn descriptions(&self) -> Vec<String> {
+        self.errors.iter()
+            .map(|error| format!("{:#}", error))
+            .collect()
+    }
+}
+
+impl Display for LoadErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} load error(s):", self.errors.len())?;
+        for description in self.descriptions() {
+            writeln!(f, "\t{}", description)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for LoadErrors {}
+
+pub fn load_json<T: DeserializeOwned>(file_path: &str) -> Result<T> {
+    let contents = read_to_string(file_path)
+        .map_err(|e| {
+            anyhow::Error::new(FileReadError {
+                file_path: file_path.to_string(),
+                source: e
+            })
+        })?;
+
+    let value: Value = serde_json::from_str(contents.as_str())
+        .map_err(|e| {
+            anyhow::Error::new(ParseJSONError {
+                file_path: file_path.to_string(),
+                source: e
+            })
+        })?;
+
+    return convert_json(&value, std::any::type_name::<T>())
+        .map_err(|e| JSONSource::new(file_path, "").locate(anyhow::Error::new(e)))
+}
+
+// A JSON file written for this run only. The file is deleted when this is dropped, so hold on to it
+// until whatever reads the path is done with it.
+#[derive(Debug)]
+pub struct TempJSON {
+    path: PathBuf
+}
+
+impl TempJSON {
+    pub fn path(&self) -> String {
+        self.path.to_string_lossy().to_string()
+    }
+}
+
+impl Drop for TempJSON {
+    fn drop(&mut self) {
+        if let Err(e) = remove_file(&self.path) {
+            warn!(file_path = %self.path.display(), "Failed to delete temporary JSON file: {}", e);
+        }
+    }
+}
+
+// Named per process and call, so games started side by side can't load each other's files.
+pub fn write_temp_json(name: &str, value: &Value) -> Result<TempJSON> {
+    let file_id = TEMP_JSON_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let path = temp_dir().join(format!("test_game_{}_{}_{}.json", name, process::id(), file_id));
+
+    write(&path, serde_json::to_string_pretty(value)?)?;
+
+    Ok(TempJSON { path })
+}
+
+// JSON merge patch (RFC 7396): objects merge recursively, null removes a key, anything else replaces.
+pub fn merge_json(target: &mut Value, patch: &Value) {
+    if let Value::Object(patch_map) = patch {
+        if !target.is_object() {
+            *target = Value::Object(Map::new());
+        }
+
+        if let Value::Object(target_map) = target {
+            for (key, patch_value) in patch_map {
+                if patch_value.is_null() {
+                    target_map.remove(key);
+                } else {
+                    merge_json(target_map.entry(key.clone()).or_insert(Value::Null), patch_value);
+                }
+            }
+        }
+    } else {
+        *target = patch.clone();
     }
 }
\ No newline at end of file
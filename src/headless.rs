@@ -0,0 +1,48 @@
+use specs::World;
+
+use anyhow::{Result, anyhow};
+
+use std::sync::{Arc, RwLock};
+
+use tracing::info;
+
+use game_engine::game::GameWrapper;
+use game_engine::load::JSONLoad;
+use crate::input::TestCustomInput;
+use crate::game::{BasicTestGameWrapper, starting_scene_stack_path, insert_resources};
+use crate::scenes::register_scene_stack;
+use crate::scenes::basic_test_scene::BasicTestSceneLoader;
+use crate::globals::load_json;
+use crate::overlays::inspector::Inspector;
+use crate::audio::AudioPlayer;
+use crate::ecs_access::WorldAccess;
+use crate::settings::Settings;
+
+// Runs the starting scene's ticks without coffee, so no window, GPU or audio device is opened.
+// Only the first scene of the stack is run, since nothing ticks long enough to finish it.
+pub fn run(settings: &Settings, ticks: u64) -> Result<()> {
+    let mut world = World::new();
+    <BasicTestGameWrapper as GameWrapper<TestCustomInput>>::register_components(&mut world);
+    insert_resources(&mut world, settings, settings.display.clone(), AudioPlayer::silent(settings.audio));
+    let ecs = Arc::new(RwLock::new(world));
+
+    let (scene_stack_path, _temp_stack) = starting_scene_stack_path(settings);
+    let scene_path = register_scene_stack(scene_stack_path.as_str())?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("Scene stack {} has no scenes", scene_stack_path))?;
+
+    let scene_json: JSONLoad = load_json(scene_path.as_str())?;
+    // The asset upload only matters for drawing, so it's dropped without running.
+    let (mut simulation, _assets) = BasicTestSceneLoader::new(scene_json)?.load_contents(ecs.clone())?;
+    info!(scene = %scene_path, ticks, "Running headless");
+
+    ecs.with_world_mut(|world| {
+        while simulation.total_ticks() < ticks {
+            simulation.step(world)?;
+        }
+
+        info!(scene = %scene_path, ticks, "Headless run finished, entity dump:\n{}", Inspector::dump(world));
+        Ok(())
+    })
+}
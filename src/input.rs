@@ -1,8 +1,15 @@
-use std::collections::HashSet;
+use std::collections::{HashSet, HashMap, BTreeMap};
 use coffee::input::keyboard::KeyCode;
 use coffee::input::{Input, keyboard};
 use coffee::input;
 
+use serde::{Deserialize, Serialize};
+
+use anyhow::Result;
+use thiserror::Error;
+
+use crate::input::InputError::UnknownKeyError;
+
 #[derive(Default, Debug)]
 pub struct TestCustomInput {
     pub keys_pressed: HashSet<KeyCode>,
@@ -10,7 +17,104 @@ pub struct TestCustomInput {
     pub keys_released: HashSet<KeyCode>,
 }
 
+#[derive(Deserialize, Serialize, Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    MoveDown
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ActionState {
+    Pressed,
+    Released
+}
+
+pub fn default_key_bindings() -> HashMap<KeyCode, Action> {
+    let mut bindings = HashMap::new();
+    bindings.insert(KeyCode::Left, Action::MoveLeft);
+    bindings.insert(KeyCode::Right, Action::MoveRight);
+    bindings.insert(KeyCode::Up, Action::MoveUp);
+    bindings.insert(KeyCode::Down, Action::MoveDown);
+
+    bindings
+}
+
+// Key names are the KeyCode variant names, e.g. "Left", "A", "Key1", "F3".
+macro_rules! key_names {
+    ($($key:ident),* $(,)?) => {
+        pub fn parse_key_code(name: &str) -> Option<KeyCode> {
+            match name {
+                $(stringify!($key) => Some(KeyCode::$key),)*
+                _ => None
+            }
+        }
+
+        pub fn key_code_name(key: KeyCode) -> Option<&'static str> {
+            match key {
+                $(KeyCode::$key => Some(stringify!($key)),)*
+                _ => None
+            }
+        }
+    };
+}
+
+key_names!(
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+    Key0, Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9,
+    F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+    Left, Right, Up, Down, Space, Return, Escape, Tab, Back,
+    LShift, RShift, LControl, RControl, LAlt, RAlt,
+    Home, End, PageUp, PageDown, Insert, Delete
+);
+
+// The form bindings take in settings.json: each action lists the names of the keys bound to it.
+pub type KeyBindingNames = BTreeMap<Action, Vec<String>>;
+
+pub fn key_binding_names(bindings: &HashMap<KeyCode, Action>) -> KeyBindingNames {
+    let mut names = KeyBindingNames::new();
+    for (key, action) in bindings {
+        if let Some(name) = key_code_name(*key) {
+            names.entry(*action).or_insert_with(Vec::new).push(name.to_string());
+        }
+    }
+
+    for keys in names.values_mut() {
+        keys.sort();
+    }
+
+    names
+}
+
+pub fn key_bindings_from_names(names: &KeyBindingNames) -> Result<HashMap<KeyCode, Action>> {
+    let mut bindings = HashMap::new();
+    for (action, keys) in names {
+        for name in keys {
+            let key = parse_key_code(name.as_str())
+                .ok_or_else(|| anyhow::Error::new(UnknownKeyError {
+                    key_name: name.clone(),
+                    action: *action
+                }))?;
+
+            bindings.insert(key, *action);
+        }
+    }
+
+    Ok(bindings)
+}
+
 impl TestCustomInput {
+    pub fn get_actions(&self, bindings: &HashMap<KeyCode, Action>) -> Vec<(Action, ActionState)> {
+        let pressed = self.keys_pressed.iter()
+            .filter_map(|key| bindings.get(key).map(|action| (*action, ActionState::Pressed)));
+        let released = self.keys_released.iter()
+            .filter_map(|key| bindings.get(key).map(|action| (*action, ActionState::Released)));
+
+        pressed.chain(released).collect()
+    }
+
     pub fn get_keys_pressed(&mut self) -> &HashSet<KeyCode> {
         &self.keys_pressed
     }
@@ -62,4 +166,13 @@ impl Input for TestCustomInput {
         self.keys_pressed.clear();
         self.keys_released.clear();
     }
-}
\ No newline at end of file
+}
+
+#[derive(Error, Debug)]
+pub enum InputError {
+    #[error("Unknown key name {key_name:?} bound to {action:?}")]
+    UnknownKeyError {
+        key_name: String,
+        action: Action
+    }
+}
@@ -0,0 +1,94 @@
+use coffee::graphics::{Frame, Font, Gpu, Text, Point, Color, Mesh, Shape, Rectangle, HorizontalAlignment, VerticalAlignment};
+use coffee::load::{LoadingScreen, Progress, Task, Join};
+
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+use tracing::debug;
+
+use crate::globals::DEBUG_FONT;
+
+const BAR_WIDTH: f32 = 600.0;
+const BAR_HEIGHT: f32 = 24.0;
+const TEXT_SIZE: f32 = 24.0;
+const SPACING: f32 = 16.0;
+
+const BAR_BACKGROUND: Color = Color { r: 0.2, g: 0.2, b: 0.2, a: 1.0 };
+const BAR_FOREGROUND: Color = Color { r: 0.3, g: 0.8, b: 0.3, a: 1.0 };
+
+// coffee only hands back the error if a task fails, so the stage that was running is kept here
+// for main to report alongside it.
+static LAST_STAGE: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
+pub fn last_stage() -> Option<String> {
+    LAST_STAGE.lock().ok().and_then(|stage| stage.clone())
+}
+
+pub fn record_stage(title: &str) {
+    if let Ok(mut last_stage) = LAST_STAGE.lock() {
+        *last_stage = Some(title.to_string());
+    }
+}
+
+// Task::stage, but the stage is recorded as the task starts rather than when the loading screen
+// next draws, so a stage that fails before it's drawn is still the one reported.
+pub fn stage<T: 'static>(title: &str, task: Task<T>) -> Task<T> {
+    let recorded = title.to_string();
+
+    Task::stage(title, (Task::succeed(move || record_stage(recorded.as_str())), task).join()
+        .map(|(_, value)| value))
+}
+
+// Shown while the game's load Task runs: a progress bar and the stage currently loading,
+// named with `stage` in BasicTestGameWrapper::load.
+pub struct TestLoadingScreen {
+    font: Font,
+    drawn_stage: Option<String>
+}
+
+impl LoadingScreen for TestLoadingScreen {
+    fn new(gpu: &mut Gpu) -> coffee::Result<Self> {
+        Ok(TestLoadingScreen {
+            font: Font::from_bytes(gpu, DEBUG_FONT)?,
+            drawn_stage: None
+        })
+    }
+
+    fn draw(&mut self, progress: &Progress, frame: &mut Frame) {
+        frame.clear(Color::BLACK);
+
+        let stage = progress.stage().cloned();
+        if self.drawn_stage != stage {
+            debug!(stage = ?stage, percentage = progress.percentage(), "Loading stage");
+            self.drawn_stage = stage.clone();
+        }
+
+        let x = (frame.width() - BAR_WIDTH) / 2.0;
+        let y = (frame.height() - BAR_HEIGHT) / 2.0;
+        let filled = BAR_WIDTH * (progress.percentage() / 100.0).max(0.0).min(1.0);
+
+        let mut bar = Mesh::new();
+        bar.fill(Shape::Rectangle(Rectangle { x, y, width: BAR_WIDTH, height: BAR_HEIGHT }), BAR_BACKGROUND);
+        bar.fill(Shape::Rectangle(Rectangle { x, y, width: filled, height: BAR_HEIGHT }), BAR_FOREGROUND);
+        bar.draw(&mut frame.as_target());
+
+        let label = format!(
+            "{} ({:.0}%)",
+            stage.unwrap_or_else(|| "Loading".to_string()),
+            progress.percentage()
+        );
+
+        self.font.add(Text {
+            content: label.as_str(),
+            position: Point::new(frame.width() / 2.0, y + BAR_HEIGHT + SPACING),
+            bounds: (BAR_WIDTH, TEXT_SIZE * 2.0),
+            size: TEXT_SIZE,
+            color: Color::WHITE,
+            horizontal_alignment: HorizontalAlignment::Center,
+            vertical_alignment: VerticalAlignment::Top
+        });
+
+        self.font.draw(&mut frame.as_target());
+    }
+}
@@ -0,0 +1,46 @@
+use tracing_subscriber::{EnvFilter, fmt, registry};
+use tracing_subscriber::prelude::*;
+use tracing_appender::non_blocking::WorkerGuard;
+
+use std::env;
+use std::path::Path;
+
+// Filter directives use the EnvFilter syntax, e.g. TEST_GAME_LOG="info,test_game::entities=debug"
+pub const LOG_FILTER_ENV: &str = "TEST_GAME_LOG";
+pub const LOG_FILE_ENV: &str = "TEST_GAME_LOG_FILE";
+
+const DEFAULT_LOG_FILTER: &str = "info";
+
+// The returned guard flushes the file writer when dropped, so keep it alive for the whole run.
+pub fn init() -> Option<WorkerGuard> {
+    let filter = EnvFilter::try_from_env(LOG_FILTER_ENV)
+        .unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_FILTER));
+
+    match env::var(LOG_FILE_ENV) {
+        Ok(file_path) => {
+            let file_path = Path::new(file_path.as_str());
+            let directory = file_path.parent().unwrap_or_else(|| Path::new("."));
+            let file_name = file_path.file_name().unwrap_or_else(|| "test_game.log".as_ref());
+
+            let (file_writer, guard) = tracing_appender::non_blocking(
+                tracing_appender::rolling::never(directory, file_name)
+            );
+
+            registry()
+                .with(filter)
+                .with(fmt::layer())
+                .with(fmt::layer().with_ansi(false).with_writer(file_writer))
+                .init();
+
+            Some(guard)
+        },
+        Err(_) => {
+            registry()
+                .with(filter)
+                .with(fmt::layer())
+                .init();
+
+            None
+        }
+    }
+}
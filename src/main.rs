@@ -3,10 +3,13 @@ extern crate game_engine;
 
 use crate::game::BasicTestGameWrapper;
 use crate::input::TestCustomInput;
+use crate::loading_screen::{TestLoadingScreen, last_stage};
+use crate::cli::Command;
 
-use coffee::graphics::WindowSettings;
 use coffee::Game;
 
+use tracing::error;
+
 use game_engine::game::MyGame;
 
 mod scenes;
@@ -15,13 +18,59 @@ mod globals;
 mod input;
 mod components;
 mod systems;
+mod entities;
+mod logging;
+mod overlays;
+mod timestep;
+mod ecs_access;
+mod events;
+mod rendering;
+mod settings;
+mod cli;
+mod random;
+mod loading_screen;
+mod assets;
+mod atlas;
+mod collision;
+mod physics;
+mod easing;
+mod audio;
+mod headless;
 
 fn main() -> coffee::Result<()> {
-    MyGame::<BasicTestGameWrapper, TestCustomInput, ()>::run( WindowSettings {
-        title: String::from("HELLO WORLD"),
-        size: (1280, 1024),
-        resizable: true,
-        fullscreen: false,
-        maximized: false,
-    })
+    let _log_guard = logging::init();
+
+    let cli = cli::options();
+    let settings_path = cli.enter_asset_root().map_err(coffee::Error::IO)?;
+    if let Some(command) = &cli.command {
+        let result = match command {
+            Command::PackAtlas { name, max_size, padding } => {
+                atlas::packer::pack_atlas(name.as_str(), *max_size, *padding).map(|_| ())
+            }
+        };
+
+        if let Err(e) = result {
+            error!("{:#}", e);
+            std::process::exit(1);
+        }
+
+        return Ok(())
+    }
+
+    let settings = settings::init(cli, settings_path);
+
+    if let Some(ticks) = cli.headless {
+        if let Err(e) = headless::run(&settings, ticks) {
+            error!("Headless run failed: {:#}", e);
+            std::process::exit(1);
+        }
+
+        return Ok(())
+    }
+
+    MyGame::<BasicTestGameWrapper, TestCustomInput, TestLoadingScreen>::run(settings.window_settings())
+        .map_err(|e| {
+            error!(stage = ?last_stage(), "Game stopped with an error: {}", e);
+            e
+        })
 }
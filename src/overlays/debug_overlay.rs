@@ -0,0 +1,79 @@
+use coffee::graphics::{Frame, Font, Text, Point, Mesh, Shape, HorizontalAlignment, VerticalAlignment};
+use coffee::load::Task;
+
+use specs::{World, WorldExt};
+
+use std::fmt::{Debug, Formatter};
+
+use crate::globals::DEBUG_FONT;
+use crate::rendering::camera::Camera;
+use crate::rendering::viewport::Viewport;
+use crate::rendering::debug_draw::{DebugDraw, DebugShape};
+
+const TEXT_SIZE: f32 = 14.0;
+const TEXT_BOUNDS: (f32, f32) = (400.0, 40.0);
+const STROKE_WIDTH: f32 = 1.0;
+
+// Draws the DebugDraw resource through the camera, on top of the world. Text is only placed through
+// the camera, so labels stay upright and readable at any zoom or rotation.
+pub struct DebugOverlay {
+    font: Font
+}
+
+impl Debug for DebugOverlay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DebugOverlay").finish()
+    }
+}
+
+impl DebugOverlay {
+    pub fn load() -> Task<DebugOverlay> {
+        Font::load_from_bytes(DEBUG_FONT)
+            .map(|font| DebugOverlay { font })
+    }
+
+    pub fn draw(&mut self, ecs: &World, frame: &mut Frame) {
+        let debug_draw = ecs.fetch::<DebugDraw>();
+        if !debug_draw.is_enabled() || debug_draw.commands().is_empty() {
+            return;
+        }
+
+        let viewport = ecs.fetch::<Viewport>();
+        let camera = ecs.fetch::<Camera>();
+        let transformation = viewport.transformation() * camera.transformation(viewport.size());
+
+        let has_shapes = debug_draw.commands().iter()
+            .any(|command| !matches!(command.shape, DebugShape::Text { .. }));
+
+        let mut mesh = Mesh::new();
+        for command in debug_draw.commands() {
+            match &command.shape {
+                DebugShape::Line { from, to } => mesh.stroke(
+                    Shape::Polyline { points: vec![Point::new(from[0], from[1]), Point::new(to[0], to[1])] },
+                    command.color,
+                    STROKE_WIDTH
+                ),
+                DebugShape::Rect { bounds } => mesh.stroke(Shape::Rectangle(*bounds), command.color, STROKE_WIDTH),
+                DebugShape::Circle { center, radius } => mesh.stroke(
+                    Shape::Circle { center: Point::new(center[0], center[1]), radius: *radius },
+                    command.color,
+                    STROKE_WIDTH
+                ),
+                DebugShape::Text { position, content } => self.font.add(Text {
+                    content: content.as_str(),
+                    position: camera.world_to_screen(Point::new(position[0], position[1]), viewport.size()),
+                    bounds: TEXT_BOUNDS,
+                    size: TEXT_SIZE,
+                    color: command.color,
+                    horizontal_alignment: HorizontalAlignment::Left,
+                    vertical_alignment: VerticalAlignment::Top
+                })
+            }
+        }
+
+        if has_shapes {
+            mesh.draw(&mut frame.as_target().transform(transformation));
+        }
+        self.font.draw(&mut frame.as_target().transform(viewport.transformation()));
+    }
+}
@@ -0,0 +1,169 @@
+use coffee::graphics::{Frame, Font, Text, Point, Color, Mesh, Shape, Rectangle, HorizontalAlignment, VerticalAlignment};
+use coffee::load::Task;
+
+use specs::{World, WorldExt, Entity, Join};
+
+use anyhow::Result;
+
+use std::fmt::{Debug, Formatter};
+
+use game_engine::load::JSONLoad;
+
+use crate::components::registry::ComponentRegistry;
+use crate::globals::DEBUG_FONT;
+
+const TEXT_SIZE: f32 = 16.0;
+const LINE_HEIGHT: f32 = 18.0;
+const MARGIN: f32 = 10.0;
+const PANEL_WIDTH: f32 = 640.0;
+
+const PANEL_COLOR: Color = Color { r: 0.0, g: 0.0, b: 0.0, a: 0.75 };
+const ENTITY_COLOR: Color = Color::WHITE;
+const SELECTED_COLOR: Color = Color { r: 1.0, g: 1.0, b: 0.0, a: 1.0 };
+const COMPONENT_COLOR: Color = Color { r: 0.7, g: 0.7, b: 0.7, a: 1.0 };
+
+pub struct Inspector {
+    font: Font,
+    visible: bool,
+    // Held by Entity so spawns and despawns elsewhere in the list don't move the selection.
+    selected: Option<Entity>
+}
+
+impl Debug for Inspector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Inspector")
+            .field("visible", &self.visible)
+            .field("selected", &self.selected)
+            .finish()
+    }
+}
+
+impl Inspector {
+    pub fn load(visible: bool) -> Task<Inspector> {
+        Font::load_from_bytes(DEBUG_FONT)
+            .map(move |font| {
+                Inspector {
+                    font,
+                    visible,
+                    selected: None
+                }
+            })
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn select_next(&mut self, ecs: &World) {
+        self.step_selection(ecs, 1);
+    }
+
+    pub fn select_previous(&mut self, ecs: &World) {
+        self.step_selection(ecs, -1);
+    }
+
+    // Starts from the first entity when nothing live is selected.
+    fn step_selection(&mut self, ecs: &World, step: isize) {
+        let entities = Self::entities(ecs);
+        if entities.is_empty() {
+            self.selected = None;
+            return;
+        }
+
+        let count = entities.len() as isize;
+        let index = self.selected
+            .and_then(|selected| entities.iter().position(|entity| *entity == selected))
+            .map_or(0, |index| (index as isize + step).rem_euclid(count) as usize);
+
+        self.selected = Some(entities[index]);
+    }
+
+    pub fn selected_entity(&self, ecs: &World) -> Option<Entity> {
+        self.selected.filter(|entity| ecs.is_alive(*entity))
+    }
+
+    pub fn apply_edit(&self, ecs: &World, edit: &JSONLoad) -> Result<()> {
+        match (self.selected_entity(ecs), ecs.try_fetch::<ComponentRegistry>()) {
+            (Some(entity), Some(registry)) => registry.apply_edit(ecs, entity, edit),
+            _ => Ok(())
+        }
+    }
+
+    fn entities(ecs: &World) -> Vec<Entity> {
+        (&ecs.entities()).join().collect()
+    }
+
+    // Headless counterpart of the overlay, suitable for logging.
+    pub fn dump(ecs: &World) -> String {
+        let registry = match ecs.try_fetch::<ComponentRegistry>() {
+            Some(registry) => registry,
+            None => return String::new()
+        };
+
+        let mut lines = Vec::new();
+        for entity in Self::entities(ecs) {
+            lines.push(format!("Entity {}:{}", entity.id(), entity.gen().id()));
+
+            for (name, description) in registry.describe_entity(ecs, entity) {
+                lines.push(format!("\t{}: {}", name, description));
+            }
+        }
+
+        lines.join("\n")
+    }
+
+    pub fn draw(&mut self, ecs: &World, frame: &mut Frame) {
+        let registry = match ecs.try_fetch::<ComponentRegistry>() {
+            Some(registry) => registry,
+            None => return
+        };
+
+        let selected_entity = self.selected_entity(ecs);
+        let mut lines: Vec<(String, Color)> = Vec::new();
+        for entity in Self::entities(ecs) {
+            let selected = Some(entity) == selected_entity;
+            let marker = if selected { ">" } else { " " };
+
+            lines.push((
+                format!("{} Entity {}:{}", marker, entity.id(), entity.gen().id()),
+                if selected { SELECTED_COLOR } else { ENTITY_COLOR }
+            ));
+
+            if selected {
+                for (name, description) in registry.describe_entity(ecs, entity) {
+                    lines.push((format!("    {}: {}", name, description), COMPONENT_COLOR));
+                }
+            }
+        }
+
+        let mut panel = Mesh::new();
+        panel.fill(
+            Shape::Rectangle(Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: PANEL_WIDTH,
+                height: MARGIN * 2.0 + LINE_HEIGHT * lines.len() as f32
+            }),
+            PANEL_COLOR
+        );
+        panel.draw(&mut frame.as_target());
+
+        for (index, (line, color)) in lines.iter().enumerate() {
+            self.font.add(Text {
+                content: line.as_str(),
+                position: Point::new(MARGIN, MARGIN + LINE_HEIGHT * index as f32),
+                bounds: (PANEL_WIDTH - MARGIN * 2.0, LINE_HEIGHT),
+                size: TEXT_SIZE,
+                color: *color,
+                horizontal_alignment: HorizontalAlignment::Left,
+                vertical_alignment: VerticalAlignment::Top
+            });
+        }
+
+        self.font.draw(&mut frame.as_target());
+    }
+}
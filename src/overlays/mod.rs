@@ -0,0 +1,3 @@
+pub mod inspector;
+pub mod options_menu;
+pub mod debug_overlay;
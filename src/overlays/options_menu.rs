@@ -0,0 +1,180 @@
+use coffee::graphics::{Frame, Font, Text, Point, Color, Mesh, Shape, Rectangle, Window, HorizontalAlignment, VerticalAlignment};
+use coffee::input::keyboard::KeyCode;
+use coffee::load::Task;
+
+use specs::{World, WorldExt};
+
+use std::fmt::{Debug, Formatter};
+
+use tracing::{info, warn};
+
+use crate::globals::DEBUG_FONT;
+use crate::input::TestCustomInput;
+use crate::settings::{self, Settings, AudioSettings};
+
+const TEXT_SIZE: f32 = 24.0;
+const LINE_HEIGHT: f32 = 32.0;
+const MARGIN: f32 = 20.0;
+const PANEL_WIDTH: f32 = 420.0;
+const VOLUME_STEP: f64 = 0.1;
+
+const PANEL_COLOR: Color = Color { r: 0.0, g: 0.0, b: 0.0, a: 0.85 };
+const ITEM_COLOR: Color = Color::WHITE;
+const SELECTED_COLOR: Color = Color { r: 1.0, g: 1.0, b: 0.0, a: 1.0 };
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum MenuItem {
+    Fullscreen,
+    MasterVolume,
+    MusicVolume,
+    SfxVolume
+}
+
+const MENU_ITEMS: [MenuItem; 4] = [
+    MenuItem::Fullscreen,
+    MenuItem::MasterVolume,
+    MenuItem::MusicVolume,
+    MenuItem::SfxVolume
+];
+
+// Up/Down pick an option, Left/Right/Return change it. Every change is saved to the settings file.
+pub struct OptionsMenu {
+    font: Font,
+    visible: bool,
+    selected: usize
+}
+
+impl Debug for OptionsMenu {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OptionsMenu")
+            .field("visible", &self.visible)
+            .field("selected", &self.selected)
+            .finish()
+    }
+}
+
+impl OptionsMenu {
+    pub fn load() -> Task<OptionsMenu> {
+        Font::load_from_bytes(DEBUG_FONT)
+            .map(|font| {
+                OptionsMenu {
+                    font,
+                    visible: false,
+                    selected: 0
+                }
+            })
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn interact(&mut self, ecs: &mut World, input: &TestCustomInput, window: &mut Window) {
+        let pressed = |key: KeyCode| input.keys_pressed.contains(&key);
+
+        if pressed(KeyCode::Down) {
+            self.selected = (self.selected + 1) % MENU_ITEMS.len();
+        }
+        if pressed(KeyCode::Up) {
+            self.selected = (self.selected + MENU_ITEMS.len() - 1) % MENU_ITEMS.len();
+        }
+
+        let step = if pressed(KeyCode::Right) {
+            VOLUME_STEP
+        } else if pressed(KeyCode::Left) {
+            -VOLUME_STEP
+        } else {
+            0.0
+        };
+
+        let item = MENU_ITEMS[self.selected];
+        let result = match item {
+            MenuItem::Fullscreen if pressed(KeyCode::Return) || step != 0.0 => {
+                let fullscreen = !settings::current().window.fullscreen;
+                window.toggle_fullscreen();
+                settings::update(move |settings| settings.window.fullscreen = fullscreen)
+            },
+            MenuItem::MasterVolume if step != 0.0 => {
+                let volume = adjust(settings::current().audio.master_volume, step);
+                settings::update(move |settings| settings.audio.master_volume = volume)
+            },
+            MenuItem::MusicVolume if step != 0.0 => {
+                let volume = adjust(settings::current().audio.music_volume, step);
+                settings::update(move |settings| settings.audio.music_volume = volume)
+            },
+            MenuItem::SfxVolume if step != 0.0 => {
+                let volume = adjust(settings::current().audio.sfx_volume, step);
+                settings::update(move |settings| settings.audio.sfx_volume = volume)
+            },
+            _ => return
+        };
+
+        match result {
+            Ok(settings) => {
+                info!(option = ?item, "Changed setting");
+                *ecs.write_resource::<AudioSettings>() = settings.audio;
+            },
+            Err(e) => warn!("Failed to save settings: {:#}", e)
+        }
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let settings = settings::current();
+        let lines: Vec<String> = MENU_ITEMS.iter().enumerate()
+            .map(|(index, item)| {
+                let marker = if index == self.selected { ">" } else { " " };
+                format!("{} {}", marker, describe(*item, &settings))
+            })
+            .collect();
+
+        let height = MARGIN * 2.0 + LINE_HEIGHT * lines.len() as f32;
+        let x = (frame.width() - PANEL_WIDTH) / 2.0;
+        let y = (frame.height() - height) / 2.0;
+
+        let mut panel = Mesh::new();
+        panel.fill(
+            Shape::Rectangle(Rectangle {
+                x,
+                y,
+                width: PANEL_WIDTH,
+                height
+            }),
+            PANEL_COLOR
+        );
+        panel.draw(&mut frame.as_target());
+
+        for (index, line) in lines.iter().enumerate() {
+            self.font.add(Text {
+                content: line.as_str(),
+                position: Point::new(x + MARGIN, y + MARGIN + LINE_HEIGHT * index as f32),
+                bounds: (PANEL_WIDTH - MARGIN * 2.0, LINE_HEIGHT),
+                size: TEXT_SIZE,
+                color: if index == self.selected { SELECTED_COLOR } else { ITEM_COLOR },
+                horizontal_alignment: HorizontalAlignment::Left,
+                vertical_alignment: VerticalAlignment::Top
+            });
+        }
+
+        self.font.draw(&mut frame.as_target());
+    }
+}
+
+fn adjust(volume: f64, step: f64) -> f64 {
+    // Rounded so repeated steps don't leave float noise in the saved file.
+    (settings::clamp_volume(volume + step) * 100.0).round() / 100.0
+}
+
+fn describe(item: MenuItem, settings: &Settings) -> String {
+    let percent = |volume: f64| (volume * 100.0).round() as u32;
+
+    match item {
+        MenuItem::Fullscreen => format!("Fullscreen: {}", if settings.window.fullscreen { "On" } else { "Off" }),
+        MenuItem::MasterVolume => format!("Master volume: {}%", percent(settings.audio.master_volume)),
+        MenuItem::MusicVolume => format!("Music volume: {}%", percent(settings.audio.music_volume)),
+        MenuItem::SfxVolume => format!("Sound effects volume: {}%", percent(settings.audio.sfx_volume))
+    }
+}
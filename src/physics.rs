@@ -0,0 +1,46 @@
+use serde::Deserialize;
+
+// World acceleration in pixels per second squared. The test game is top-down, so it defaults to none;
+// a platformer scene sets e.g. `"gravity": [0, 980]` in its scene values.
+#[derive(Deserialize, Debug, Copy, Clone, Default, PartialEq)]
+pub struct Gravity(pub [f32; 2]);
+
+// Removes the part of `velocity` heading into a surface with outward `normal`, bouncing back
+// `restitution` of it (0 stops dead, 1 is a perfect bounce). Friction takes `friction` times the
+// speed into the surface off the speed along it, never reversing it, so a body resting on the
+// ground under gravity slows at `friction` times that gravity.
+pub fn reflect(velocity: [f32; 2], normal: [f32; 2], restitution: f32, friction: f32) -> [f32; 2] {
+    let into_surface = velocity[0] * normal[0] + velocity[1] * normal[1];
+    if into_surface >= 0.0 {
+        return velocity;
+    }
+
+    let along = [velocity[0] - into_surface * normal[0], velocity[1] - into_surface * normal[1]];
+    let sliding = (along[0] * along[0] + along[1] * along[1]).sqrt();
+    let kept = if sliding > 0.0 {
+        (1.0 - friction * -into_surface / sliding).max(0.0)
+    } else {
+        0.0
+    };
+
+    let bounce = -restitution * into_surface;
+    [along[0] * kept + bounce * normal[0], along[1] * kept + bounce * normal[1]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reflect_bounces_only_the_speed_into_the_surface() {
+        assert_eq!(reflect([3.0, 4.0], [0.0, -1.0], 0.5, 0.0), [3.0, -2.0]);
+        assert_eq!(reflect([3.0, -4.0], [0.0, -1.0], 0.5, 0.0), [3.0, -4.0]);
+    }
+
+    #[test]
+    fn friction_slows_sliding_in_proportion_to_the_impact() {
+        assert_eq!(reflect([10.0, 4.0], [0.0, -1.0], 0.0, 0.5), [8.0, 0.0]);
+        // Strong enough friction stops the slide but never pushes the body back.
+        assert_eq!(reflect([1.0, 4.0], [0.0, -1.0], 0.0, 1.0), [0.0, 0.0]);
+    }
+}
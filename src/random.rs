@@ -0,0 +1,37 @@
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+use tracing::info;
+
+// The one source of randomness for game logic, so a logged seed replays a run exactly.
+#[derive(Debug, Clone)]
+pub struct GameRng {
+    seed: u64,
+    rng: StdRng
+}
+
+impl GameRng {
+    pub fn new(seed: Option<u64>) -> Self {
+        let seed = seed.unwrap_or_else(|| rand::thread_rng().gen());
+        info!(seed, "Seeded game RNG");
+
+        GameRng {
+            seed,
+            rng: StdRng::seed_from_u64(seed)
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        GameRng::new(None)
+    }
+}
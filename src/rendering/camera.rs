@@ -0,0 +1,137 @@
+use coffee::graphics::{Transformation, Vector, Point};
+
+use serde::Deserialize;
+
+// World units are pixels at zoom 1. The camera's position is the world point drawn at the
+// centre of the frame.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Camera {
+    #[serde(default)]
+    pub position: [f32; 2],
+    #[serde(default = "default_zoom")]
+    pub zoom: f32,
+    // Radians, clockwise on screen.
+    #[serde(default)]
+    pub rotation: f32,
+    #[serde(default)]
+    pub follow: Option<CameraFollow>,
+    // Position before the latest tick, for interpolated draws.
+    #[serde(skip)]
+    pub previous_position: Option<[f32; 2]>
+}
+
+// Follows the PlayerControl entity once it leaves a box of `dead_zone` around the camera centre.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct CameraFollow {
+    #[serde(default)]
+    pub dead_zone: [f32; 2],
+    // Per second; higher catches up faster, 0 snaps straight to the target.
+    #[serde(default)]
+    pub smoothing: f32
+}
+
+fn default_zoom() -> f32 {
+    1.0
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Camera {
+            position: [0.0, 0.0],
+            zoom: default_zoom(),
+            rotation: 0.0,
+            follow: None,
+            previous_position: None
+        }
+    }
+}
+
+impl Camera {
+    // This camera as seen `t` of the way from its previous tick to its current one.
+    pub fn interpolated(&self, t: f32) -> Camera {
+        let mut camera = self.clone();
+        if let Some(previous) = self.previous_position {
+            for axis in 0..2 {
+                camera.position[axis] = previous[axis] + (self.position[axis] - previous[axis]) * t;
+            }
+        }
+
+        camera
+    }
+
+    pub fn transformation(&self, viewport: (f32, f32)) -> Transformation {
+        Transformation::translate(Vector::new(viewport.0 / 2.0, viewport.1 / 2.0))
+            * Transformation::rotate(self.rotation)
+            * Transformation::scale(self.zoom)
+            * Transformation::translate(Vector::new(-self.position[0], -self.position[1]))
+    }
+
+    // Where `transformation` puts a world point, for things that are placed through the camera but
+    // shouldn't be zoomed or rotated by it, like debug labels.
+    pub fn world_to_screen(&self, point: Point, viewport: (f32, f32)) -> Point {
+        let (sin, cos) = self.rotation.sin_cos();
+        let x = (point.x - self.position[0]) * self.zoom;
+        let y = (point.y - self.position[1]) * self.zoom;
+
+        Point::new(
+            x * cos - y * sin + viewport.0 / 2.0,
+            x * sin + y * cos + viewport.1 / 2.0
+        )
+    }
+
+    // Moves towards `target` by however much of the dead zone it has left, eased by `smoothing`.
+    pub fn follow_target(&mut self, target: [f32; 2], delta_time: f32) {
+        let follow = match self.follow {
+            Some(follow) => follow,
+            None => return
+        };
+
+        for axis in 0..2 {
+            let half_zone = follow.dead_zone[axis] / 2.0;
+            let offset = target[axis] - self.position[axis];
+
+            let excess = if offset > half_zone {
+                offset - half_zone
+            } else if offset < -half_zone {
+                offset + half_zone
+            } else {
+                0.0
+            };
+
+            let blend = if follow.smoothing > 0.0 {
+                1.0 - (-follow.smoothing * delta_time).exp()
+            } else {
+                1.0
+            };
+
+            self.position[axis] += excess * blend;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(actual: Point, expected: [f32; 2]) {
+        assert!(
+            (actual.x - expected[0]).abs() < 1e-3 && (actual.y - expected[1]).abs() < 1e-3,
+            "{:?} isn't near {:?}", actual, expected
+        );
+    }
+
+    #[test]
+    fn world_to_screen_centres_the_camera_position() {
+        let camera = Camera { position: [100.0, -50.0], zoom: 3.0, rotation: 1.0, ..Camera::default() };
+
+        assert_near(camera.world_to_screen(Point::new(100.0, -50.0), (800.0, 600.0)), [400.0, 300.0]);
+    }
+
+    #[test]
+    fn world_to_screen_zooms_then_rotates_clockwise() {
+        let camera = Camera { position: [10.0, 10.0], zoom: 2.0, rotation: std::f32::consts::FRAC_PI_2, ..Camera::default() };
+
+        // 5px right of the camera is 10px right at zoom 2, then a quarter turn clockwise puts it below.
+        assert_near(camera.world_to_screen(Point::new(15.0, 10.0), (200.0, 100.0)), [100.0, 60.0]);
+    }
+}
@@ -0,0 +1,83 @@
+use coffee::graphics::{Color, Rectangle};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DebugShape {
+    Line {
+        from: [f32; 2],
+        to: [f32; 2]
+    },
+    Rect {
+        bounds: Rectangle<f32>
+    },
+    Circle {
+        center: [f32; 2],
+        radius: f32
+    },
+    Text {
+        position: [f32; 2],
+        content: String
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugCommand {
+    pub shape: DebugShape,
+    pub color: Color
+}
+
+// Immediate-mode debug shapes in world coordinates. Any system can push into it during a tick; the
+// scene clears it before each update that runs ticks, so the DebugOverlay draws every tick since the
+// last draw. While disabled, pushes are dropped so systems don't need to check first.
+#[derive(Debug, Default)]
+pub struct DebugDraw {
+    enabled: bool,
+    commands: Vec<DebugCommand>
+}
+
+impl DebugDraw {
+    pub fn new(enabled: bool) -> Self {
+        DebugDraw {
+            enabled,
+            commands: Vec::new()
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+        self.commands.clear();
+    }
+
+    pub fn clear(&mut self) {
+        self.commands.clear();
+    }
+
+    pub fn commands(&self) -> &[DebugCommand] {
+        &self.commands
+    }
+
+    pub fn line(&mut self, from: [f32; 2], to: [f32; 2], color: Color) {
+        self.push(DebugShape::Line { from, to }, color);
+    }
+
+    pub fn rect(&mut self, bounds: Rectangle<f32>, color: Color) {
+        self.push(DebugShape::Rect { bounds }, color);
+    }
+
+    pub fn circle(&mut self, center: [f32; 2], radius: f32, color: Color) {
+        self.push(DebugShape::Circle { center, radius }, color);
+    }
+
+    pub fn text(&mut self, position: [f32; 2], content: &str, color: Color) {
+        self.push(DebugShape::Text { position, content: content.to_string() }, color);
+    }
+
+    fn push(&mut self, shape: DebugShape, color: Color) {
+        if self.enabled {
+            self.commands.push(DebugCommand { shape, color });
+        }
+    }
+}
@@ -0,0 +1,445 @@
+use coffee::graphics::{Frame, Text, Point, Transformation, Vector, Mesh, Shape, Color, Batch, Sprite, Rectangle, Image};
+
+use specs::prelude::*;
+
+use tracing::debug;
+
+use std::cmp::Ordering;
+
+use crate::components::mesh_graphic::MeshGraphic;
+use crate::components::drawable::Drawable;
+use crate::components::text_display::TextDisplay;
+use crate::components::position::Position;
+use crate::components::previous_position::PreviousPosition;
+use crate::components::screen_space::ScreenSpace;
+use crate::components::anchor::Anchor;
+use crate::components::sprite::AtlasSprite;
+use crate::components::animation::Animation;
+use crate::components::tilemap::Tilemap;
+use crate::components::particle_emitter::ParticleEmitter;
+use crate::components::render_layer::RenderLayer;
+use crate::rendering::camera::Camera;
+use crate::timestep::Interpolation;
+use crate::rendering::viewport::Viewport;
+use crate::rendering::layers::RenderLayers;
+use crate::assets::SceneAssets;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum DrawKind {
+    Tilemap,
+    Drawable,
+    Mesh,
+    Sprite,
+    Animation,
+    Particles,
+    Text
+}
+
+impl DrawKind {
+    // Sprites and animation frames come out of the same atlas pages, so they can share batches.
+    fn batch_group(&self) -> DrawKind {
+        match self {
+            DrawKind::Animation => DrawKind::Sprite,
+            kind => *kind
+        }
+    }
+}
+
+// Batches kept in the order their first sprite was added, so a run draws the same way every frame.
+// Runs only touch a handful of images, so lookups just scan.
+struct OrderedBatches<K> {
+    batches: Vec<(K, Batch)>
+}
+
+impl<K: PartialEq> OrderedBatches<K> {
+    fn new() -> Self {
+        OrderedBatches { batches: Vec::new() }
+    }
+
+    fn entry(&mut self, key: K, image: &Image) -> &mut Batch {
+        let index = match self.batches.iter().position(|(existing, _)| *existing == key) {
+            Some(index) => index,
+            None => {
+                self.batches.push((key, Batch::new(image.clone())));
+                self.batches.len() - 1
+            }
+        };
+
+        &mut self.batches[index].1
+    }
+
+    fn values(&self) -> impl Iterator<Item = &Batch> {
+        self.batches.iter().map(|(_, batch)| batch)
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct DrawItem {
+    entity: Entity,
+    kind: DrawKind,
+    layer: usize,
+    z: f32,
+    screen: bool
+}
+
+// Draws Tilemap, Drawable, MeshGraphic, AtlasSprite, Animation, ParticleEmitter and TextDisplay relative to their entity's Position (if any), through the
+// camera unless the entity is marked ScreenSpace. Both are authored in the viewport's virtual resolution.
+// Everything is sorted by RenderLayer then z; ties keep world under screen space and the old per-kind order.
+// Positions and the camera are blended from the previous tick by Interpolation, so motion stays smooth
+// when frames and ticks don't line up.
+pub struct DrawWorld<'a, 'b> {
+    pub frame: &'a mut Frame<'b>,
+    pub assets: &'a SceneAssets
+}
+
+impl<'a, 'b, 'c> System<'c> for DrawWorld<'a, 'b> {
+    type SystemData = (
+        Entities<'c>,
+        Read<'c, Camera>,
+        Read<'c, Interpolation>,
+        Read<'c, Viewport>,
+        Read<'c, RenderLayers>,
+        ReadStorage<'c, RenderLayer>,
+        ReadStorage<'c, Position>,
+        ReadStorage<'c, PreviousPosition>,
+        ReadStorage<'c, ScreenSpace>,
+        ReadStorage<'c, Anchor>,
+        ReadStorage<'c, Tilemap>,
+        ReadStorage<'c, Drawable>,
+        ReadStorage<'c, MeshGraphic>,
+        ReadStorage<'c, AtlasSprite>,
+        ReadStorage<'c, Animation>,
+        ReadStorage<'c, ParticleEmitter>,
+        ReadStorage<'c, TextDisplay>,
+    );
+
+    fn run(&mut self, (entities, camera, interpolation, viewport, render_layers, layers, pos, previous, screen, anchors, tilemaps, drawables, meshes, sprites, animations, emitters, texts): Self::SystemData) {
+        let frame = &mut *self.frame;
+        let screen_transformation = viewport.transformation();
+        let camera = camera.interpolated(interpolation.0);
+        let world_transformation = screen_transformation * camera.transformation(viewport.size());
+
+        // Anchors only apply to ScreenSpace entities; the world has no edges to anchor to.
+        let offset = |pos: Option<&Position>, screen: Option<&ScreenSpace>, anchor: Option<&Anchor>| {
+            let (x, y) = pos.map(|p| (p.x, p.y)).unwrap_or((0.0, 0.0));
+            let (anchor_x, anchor_y) = match (screen, anchor) {
+                (Some(_), Some(anchor)) => anchor.origin(viewport.size()),
+                _ => (0.0, 0.0)
+            };
+
+            (x + anchor_x, y + anchor_y)
+        };
+        let place = |entity: Entity| {
+            let interpolated = pos.get(entity)
+                .map(|current| PreviousPosition::interpolate(previous.get(entity), current, interpolation.0));
+            offset(interpolated.as_ref(), screen.get(entity), anchors.get(entity))
+        };
+
+        let mut items = Vec::new();
+        let mut push = |entity: Entity, kind: DrawKind| {
+            let layer = layers.get(entity);
+            items.push(DrawItem {
+                entity,
+                kind,
+                layer: render_layers.index(layer.map(|layer| layer.layer.as_str())),
+                z: layer.map_or(0.0, |layer| layer.z),
+                screen: screen.get(entity).is_some()
+            });
+        };
+
+        for (entity, _) in (&entities, &tilemaps).join() {
+            push(entity, DrawKind::Tilemap);
+        }
+        for (entity, _) in (&entities, &drawables).join() {
+            push(entity, DrawKind::Drawable);
+        }
+        for (entity, _) in (&entities, &meshes).join() {
+            push(entity, DrawKind::Mesh);
+        }
+        for (entity, _) in (&entities, &sprites).join() {
+            push(entity, DrawKind::Sprite);
+        }
+        for (entity, _) in (&entities, &animations).join() {
+            push(entity, DrawKind::Animation);
+        }
+        for (entity, _) in (&entities, &emitters).join() {
+            push(entity, DrawKind::Particles);
+        }
+        for (entity, _) in (&entities, &texts).join() {
+            push(entity, DrawKind::Text);
+        }
+
+        items.sort_by(|a, b| {
+            a.layer.cmp(&b.layer)
+                .then(a.z.partial_cmp(&b.z).unwrap_or(Ordering::Equal))
+                .then(a.screen.cmp(&b.screen))
+                .then(a.kind.cmp(&b.kind))
+                .then(a.entity.id().cmp(&b.entity.id()))
+        });
+
+        // Consecutive items of the same kind and space form a run, drawn with one batch per image.
+        // Items sharing an image keep their sorted order inside its batch, and batches draw in the order
+        // their images first appear; only items on different images (or fonts) within a run may draw
+        // out of order.
+        self.assets.with_loaded(|assets| {
+            let mut start = 0;
+            while start < items.len() {
+                let first = items[start];
+                let run_length = items[start..].iter()
+                    .take_while(|item| item.kind.batch_group() == first.kind.batch_group() && item.screen == first.screen)
+                    .count();
+                let run = &items[start..start + run_length];
+                start += run_length;
+
+                let base = if first.screen { screen_transformation } else { world_transformation };
+
+                match first.kind.batch_group() {
+                    DrawKind::Tilemap => {
+                        // One batch per tileset image.
+                        let mut tilemap_batches = OrderedBatches::new();
+                        for item in run {
+                            let tilemap = match tilemaps.get(item.entity) {
+                                Some(tilemap) => tilemap,
+                                None => continue
+                            };
+                            let (x, y) = place(item.entity);
+
+                            for (tileset, source, [tile_x, tile_y]) in tilemap.tiles() {
+                                let image = match assets.image(tileset.image.as_str()) {
+                                    Some(image) => image,
+                                    None => continue
+                                };
+
+                                tilemap_batches.entry(tileset.image.as_str(), image)
+                                    .add(Sprite {
+                                        source,
+                                        position: Point::new(x + tile_x, y + tile_y),
+                                        scale: (1.0, 1.0)
+                                    });
+                            }
+                        }
+
+                        for batch in tilemap_batches.values() {
+                            batch.draw(&mut frame.as_target().transform(base));
+                        }
+                    },
+                    DrawKind::Drawable => {
+                        // Each Drawable's shapes draw straight away; its strips are batched per image and
+                        // its text queued, so a run's sprites sit above its shapes and its text above both.
+                        let mut drawable_batches = OrderedBatches::new();
+                        let mut has_text = false;
+                        for item in run {
+                            let drawable = match drawables.get(item.entity) {
+                                Some(drawable) => drawable,
+                                None => continue
+                            };
+                            let (x, y) = place(item.entity);
+
+                            if let Some(mesh) = &drawable.mesh {
+                                mesh.draw(&mut frame.as_target().transform(base * Transformation::translate(Vector::new(x, y))));
+                            }
+
+                            for sprite in &drawable.sprites {
+                                let image = match assets.image(sprite.image.as_str()) {
+                                    Some(image) => image,
+                                    None => {
+                                        debug!(image = %sprite.image, "Drawable uses an image its scene doesn't declare");
+                                        continue;
+                                    }
+                                };
+
+                                drawable_batches.entry(sprite.image.as_str(), image)
+                                    .add(Sprite {
+                                        source: sprite.frame_source(),
+                                        position: Point::new(x + sprite.position[0], y + sprite.position[1]),
+                                        scale: (sprite.scale[0], sprite.scale[1])
+                                    });
+                            }
+
+                            for text in &drawable.texts {
+                                let font = match assets.font_mut(text.font.as_str()) {
+                                    Some(font) => font,
+                                    None => {
+                                        debug!(font = %text.font, "Drawable uses a font its scene doesn't declare");
+                                        continue;
+                                    }
+                                };
+
+                                let content = text.content.join("\n");
+                                font.add(Text {
+                                    content: content.as_str(),
+                                    position: Point::new(text.position.x + x, text.position.y + y),
+                                    bounds: text.bounds,
+                                    size: text.size,
+                                    color: text.color,
+                                    horizontal_alignment: text.h_align,
+                                    vertical_alignment: text.v_align
+                                });
+                                has_text = true;
+                            }
+                        }
+
+                        for batch in drawable_batches.values() {
+                            batch.draw(&mut frame.as_target().transform(base));
+                        }
+                        if has_text {
+                            assets.draw_fonts(&mut frame.as_target().transform(base));
+                        }
+                    },
+                    DrawKind::Mesh => {
+                        for item in run {
+                            if let Some(mesh) = meshes.get(item.entity) {
+                                let (x, y) = place(item.entity);
+                                let mut target = frame.as_target();
+                                mesh.mesh.draw(&mut target.transform(base * Transformation::translate(Vector::new(x, y))));
+                            }
+                        }
+                    },
+                    DrawKind::Sprite => {
+                        // Batched per atlas page.
+                        let mut batches = OrderedBatches::new();
+                        for item in run {
+                            let region = match item.kind {
+                                DrawKind::Animation => animations.get(item.entity).and_then(|animation| {
+                                    assets.region(animation.region.as_str())
+                                        .map(|(key, region)| (
+                                            key,
+                                            region.frame(animation.current_frame, animation.frame_size),
+                                            animation.offset,
+                                            animation.scale
+                                        ))
+                                }),
+                                _ => sprites.get(item.entity).and_then(|sprite| {
+                                    assets.region(sprite.region.as_str())
+                                        .map(|(key, region)| (key, region.source(), sprite.offset, sprite.scale))
+                                })
+                            };
+                            let (key, source, sprite_offset, scale) = match region {
+                                Some(region) => region,
+                                None => continue
+                            };
+
+                            let page = match assets.atlas_page(key) {
+                                Some(page) => page,
+                                None => continue
+                            };
+
+                            let (x, y) = place(item.entity);
+                            batches.entry((key.0.to_string(), key.1), page)
+                                .add(Sprite {
+                                    source,
+                                    position: Point::new(x + sprite_offset[0], y + sprite_offset[1]),
+                                    scale: (scale[0], scale[1])
+                                });
+                        }
+
+                        for batch in batches.values() {
+                            batch.draw(&mut frame.as_target().transform(base));
+                        }
+                    },
+                    DrawKind::Particles => {
+                        // Particles are already in world (or screen) coordinates, so only the anchor is added.
+                        // Image particles are batched per image; the rest are coloured squares in one mesh.
+                        let mut particle_batches = OrderedBatches::new();
+                        let mut particle_mesh = Mesh::new();
+                        let mut has_squares = false;
+                        for item in run {
+                            let emitter = match emitters.get(item.entity) {
+                                Some(emitter) => emitter,
+                                None => continue
+                            };
+
+                            let (x, y) = offset(None, screen.get(item.entity), anchors.get(item.entity));
+                            let image = emitter.image.as_ref()
+                                .and_then(|name| assets.image(name.as_str()).map(|image| (name.as_str(), image)));
+
+                            match image {
+                                Some((name, image)) => {
+                                    let (width, height) = (image.width(), image.height());
+                                    let batch = particle_batches.entry(name, image);
+
+                                    for particle in &emitter.particles {
+                                        let scale = emitter.scale(particle);
+                                        batch.add(Sprite {
+                                            source: Rectangle { x: 0, y: 0, width, height },
+                                            position: Point::new(
+                                                x + particle.position[0] - width as f32 * scale / 2.0,
+                                                y + particle.position[1] - height as f32 * scale / 2.0
+                                            ),
+                                            scale: (scale, scale)
+                                        });
+                                    }
+                                },
+                                None => {
+                                    for particle in &emitter.particles {
+                                        let size = emitter.size * emitter.scale(particle);
+                                        let [r, g, b, a] = emitter.color(particle);
+                                        particle_mesh.fill(
+                                            Shape::Rectangle(Rectangle {
+                                                x: x + particle.position[0] - size / 2.0,
+                                                y: y + particle.position[1] - size / 2.0,
+                                                width: size,
+                                                height: size
+                                            }),
+                                            Color::new(r, g, b, a)
+                                        );
+                                        has_squares = true;
+                                    }
+                                }
+                            }
+                        }
+
+                        for batch in particle_batches.values() {
+                            batch.draw(&mut frame.as_target().transform(base));
+                        }
+                        if has_squares {
+                            particle_mesh.draw(&mut frame.as_target().transform(base));
+                        }
+                    },
+                    DrawKind::Text => {
+                        // Fonts flush everything queued on a draw, so a run of text is one draw.
+                        for item in run {
+                            let text = match texts.get(item.entity) {
+                                Some(text) => text,
+                                None => continue
+                            };
+
+                            let font = match assets.font_mut(text.font.as_str()) {
+                                Some(font) => font,
+                                None => {
+                                    debug!(font = %text.font, "TextDisplay uses a font its scene doesn't declare");
+                                    continue;
+                                }
+                            };
+
+                            let (x, y) = place(item.entity);
+                            let content = text.content.join("\n");
+
+                            font.add(Text {
+                                content: content.as_str(),
+                                position: Point::new(text.position.x + x, text.position.y + y),
+                                bounds: text.bounds,
+                                size: text.size,
+                                color: text.color,
+                                horizontal_alignment: text.h_align,
+                                vertical_alignment: text.v_align
+                            });
+                        }
+
+                        assets.draw_fonts(&mut frame.as_target().transform(base));
+                    },
+                    DrawKind::Animation => ()
+                }
+            }
+        });
+
+        let bars = viewport.bars();
+        if !bars.is_empty() {
+            let mut mesh = Mesh::new();
+            for bar in bars {
+                mesh.fill(Shape::Rectangle(bar), Color::BLACK);
+            }
+            mesh.draw(&mut frame.as_target());
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use serde::Deserialize;
+
+use anyhow::Result;
+use thiserror::Error;
+
+use crate::rendering::layers::RenderLayerError::{UnknownLayerError, UnknownDefaultLayerError};
+
+// The scene's draw layers, back to front.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct RenderLayers {
+    pub names: Vec<String>,
+    pub default_layer: String
+}
+
+impl Default for RenderLayers {
+    fn default() -> Self {
+        RenderLayers {
+            names: vec!["background".to_string(), "world".to_string(), "ui".to_string()],
+            default_layer: "world".to_string()
+        }
+    }
+}
+
+impl RenderLayers {
+    pub fn validate(&self) -> Result<()> {
+        if !self.names.contains(&self.default_layer) {
+            return Err(anyhow::Error::new(UnknownDefaultLayerError {
+                layer: self.default_layer.clone(),
+                names: self.names.clone()
+            }));
+        }
+
+        Ok(())
+    }
+
+    pub fn check(&self, layer: &str) -> Result<()> {
+        if !self.names.iter().any(|name| name == layer) {
+            return Err(anyhow::Error::new(UnknownLayerError {
+                layer: layer.to_string(),
+                names: self.names.clone()
+            }));
+        }
+
+        Ok(())
+    }
+
+    // Layers are checked when the scene loads, so an unknown name here came from a later spawn
+    // and falls back to the default rather than vanishing.
+    pub fn index(&self, layer: Option<&str>) -> usize {
+        let layer = layer.unwrap_or(self.default_layer.as_str());
+
+        self.names.iter().position(|name| name == layer)
+            .or_else(|| self.names.iter().position(|name| *name == self.default_layer))
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum RenderLayerError {
+    #[error("Unknown render layer {layer:?}, expected one of {names:?}")]
+    UnknownLayerError {
+        layer: String,
+        names: Vec<String>
+    },
+    #[error("Default render layer {layer:?} isn't one of {names:?}")]
+    UnknownDefaultLayerError {
+        layer: String,
+        names: Vec<String>
+    }
+}
@@ -0,0 +1,5 @@
+pub mod camera;
+pub mod debug_draw;
+pub mod draw_world;
+pub mod layers;
+pub mod viewport;
@@ -0,0 +1,114 @@
+use coffee::graphics::{Transformation, Vector, Rectangle};
+
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_VIRTUAL_SIZE: [f32; 2] = [1280.0, 1024.0];
+
+#[derive(Deserialize, Serialize, Debug, Copy, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScaleMode {
+    // Keeps the virtual aspect ratio and fills the rest of the window with bars.
+    Letterbox,
+    // Fills the window, distorting the image if the aspect ratios differ.
+    Stretch,
+    // Keeps the aspect ratio and shows more of the world instead of drawing bars.
+    Expand
+}
+
+// Maps the virtual resolution that scenes and UI are authored in onto the real window.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Viewport {
+    pub virtual_size: [f32; 2],
+    pub scale_mode: ScaleMode,
+    #[serde(skip)]
+    window_size: [f32; 2]
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Viewport::new(DEFAULT_VIRTUAL_SIZE, ScaleMode::Letterbox)
+    }
+}
+
+impl Viewport {
+    pub fn new(virtual_size: [f32; 2], scale_mode: ScaleMode) -> Self {
+        Viewport {
+            virtual_size,
+            scale_mode,
+            window_size: virtual_size
+        }
+    }
+
+    pub fn window_size(&self) -> [f32; 2] {
+        self.window_size
+    }
+
+    // Returns whether the size actually changed.
+    pub fn resize(&mut self, width: f32, height: f32) -> bool {
+        let changed = self.window_size != [width, height];
+        self.window_size = [width, height];
+        changed
+    }
+
+    fn uniform_scale(&self) -> f32 {
+        (self.window_size[0] / self.virtual_size[0]).min(self.window_size[1] / self.virtual_size[1])
+    }
+
+    // Size of the visible area in virtual units; only Expand lets it differ from virtual_size.
+    pub fn size(&self) -> (f32, f32) {
+        match self.scale_mode {
+            ScaleMode::Expand => {
+                let scale = self.uniform_scale();
+                (self.window_size[0] / scale, self.window_size[1] / scale)
+            },
+            _ => (self.virtual_size[0], self.virtual_size[1])
+        }
+    }
+
+    pub fn transformation(&self) -> Transformation {
+        match self.scale_mode {
+            ScaleMode::Letterbox => {
+                let scale = self.uniform_scale();
+                let (x, y) = self.letterbox_offset(scale);
+
+                Transformation::translate(Vector::new(x, y)) * Transformation::scale(scale)
+            },
+            ScaleMode::Stretch => Transformation::nonuniform_scale(Vector::new(
+                self.window_size[0] / self.virtual_size[0],
+                self.window_size[1] / self.virtual_size[1]
+            )),
+            ScaleMode::Expand => Transformation::scale(self.uniform_scale())
+        }
+    }
+
+    fn letterbox_offset(&self, scale: f32) -> (f32, f32) {
+        (
+            (self.window_size[0] - self.virtual_size[0] * scale) / 2.0,
+            (self.window_size[1] - self.virtual_size[1] * scale) / 2.0
+        )
+    }
+
+    // The bars around the virtual area, in window pixels. Empty unless letterboxing.
+    pub fn bars(&self) -> Vec<Rectangle<f32>> {
+        if self.scale_mode != ScaleMode::Letterbox {
+            return Vec::new()
+        }
+
+        let [width, height] = self.window_size;
+        let (x, y) = self.letterbox_offset(self.uniform_scale());
+
+        let bars = if x > 0.0 {
+            vec![
+                Rectangle { x: 0.0, y: 0.0, width: x, height },
+                Rectangle { x: width - x, y: 0.0, width: x, height }
+            ]
+        } else {
+            vec![
+                Rectangle { x: 0.0, y: 0.0, width, height: y },
+                Rectangle { x: 0.0, y: height - y, width, height: y }
+            ]
+        };
+
+        bars.into_iter().filter(|bar| bar.width > 0.0 && bar.height > 0.0).collect()
+    }
+}
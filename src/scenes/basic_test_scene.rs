@@ -1,43 +1,76 @@
-use coffee::graphics::{Window, Frame, Text, Color};
+use coffee::graphics::{Window, Frame, Font, Text, Color};
 use coffee::{Timer};
-use coffee::load::{Task, Join};
+use coffee::load::Task;
 
 use serde::Deserialize;
-use serde_json::{Value, from_value};
 
-use std::io::ErrorKind;
+use anyhow::{Result, Error, anyhow};
 
-use anyhow::{Result, Error};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
 
-use std::sync::{Arc, RwLock};
-
-use game_engine::load::{JSONLoad, load_entity_vec, build_task_error};
+use game_engine::load::JSONLoad;
 use game_engine::scenes::{Scene, SceneLoaderJSON, SceneLoader};
 use game_engine::scenes::scene_stack::SceneTransition;
-use crate::input::TestCustomInput;
-use specs::{World, Entity, RunNow};
-use crate::components::BasicTestComponentMux;
-use crate::globals::TestGlobalError::{LoadIDMatchError, ConvertJSONError, ECSWriteError, ECSReadError};
-use crate::systems::print_basic_components::PrintBasicComponents;
+use crate::input::{TestCustomInput, Action};
+use crate::events::{GameEvent, GameEvents};
+use std::collections::HashMap;
+use specs::{World, WorldExt, Entity, RunNow};
+use crate::entities::{load_entity_vec, load_entity_instances, EntityInstanceJSON};
+use crate::entities::spawn_queue::{EntitySpawner, SpawnQueue, SpawnRequest};
+use crate::globals::TestGlobalError::LoadIDMatchError;
+use crate::ecs_access::WorldAccess;
+use crate::overlays::inspector::Inspector;
+use crate::overlays::options_menu::OptionsMenu;
+use crate::overlays::debug_overlay::DebugOverlay;
+use crate::settings;
+use crate::cli;
+use crate::globals::{load_json, convert_json, JSONSource, LoadErrors, DEBUG_FONT, LOAD_PATH, JSON_ASSETS_DIR, DEBUG_DIR, JSON_FILE};
+use crate::scenes::{SCENES_DIR, scene_path};
+use crate::scenes::load_error_scene::LoadErrorScene;
 use std::ops::Deref;
-use game_engine::systems::draw_basic::DrawBasic;
-use game_engine::systems::animate_sprites::AnimateSprites;
-use game_engine::systems::play_default_sounds::PlayDefaultSounds;
+use crate::systems::{SceneDispatcher, SystemJSON, default_systems};
 use coffee::input::keyboard::KeyCode;
+use tracing::{info, debug, error, info_span};
+use crate::timestep::{FixedTimestep, DeltaTime, Interpolation, RecordPreviousPositions, DEFAULT_TICK_RATE};
+use crate::rendering::camera::Camera;
+use crate::physics::Gravity;
+use crate::rendering::viewport::Viewport;
+use crate::rendering::layers::RenderLayers;
+use crate::rendering::debug_draw::DebugDraw;
+use crate::components::render_layer::RenderLayer;
+use crate::components::tilemap::Tilemap;
+use crate::rendering::draw_world::DrawWorld;
+use crate::assets::{AssetCache, AssetCatalog, AssetUpload, SceneAssets, SceneAssetsJSON};
+use crate::loading_screen::stage;
+use coffee::load::Join;
 
 #[derive(Deserialize, Debug)]
 pub struct BasicTestSceneLoader {
     scene_json: JSONLoad,
+    scene_path: String
 }
 
-const LAST_FRAME: usize = 300;
+const DUMP_AFTER_SECONDS: usize = 5;
+const SPAWN_PREFAB_ID: &str = "basic_entity";
+const INSPECTOR_EDIT_FILE_ID: &str = "inspector_edit";
 pub const BASIC_TEST_SCENE_FILE_ID: &str = "basic_test_scene";
+const SCENE_NAME: &str = "BASIC TEST SCENE";
 
 impl BasicTestSceneLoader {
     pub fn new(json: JSONLoad) -> Result<Self> {
         return if json.load_type_id == BASIC_TEST_SCENE_FILE_ID {
+            // Falls back to the conventional location for scenes not loaded through a registered stack.
+            let scene_path = scene_path(&json).unwrap_or_else(|| [
+                LOAD_PATH,
+                JSON_ASSETS_DIR,
+                SCENES_DIR,
+                BASIC_TEST_SCENE_FILE_ID,
+                JSON_FILE
+            ].join(""));
+
             Ok(Self {
-                scene_json: json
+                scene_json: json,
+                scene_path
             })
         } else {
             Err(Error::new(LoadIDMatchError {
@@ -48,112 +81,449 @@ impl BasicTestSceneLoader {
     }
 }
 
-impl SceneLoader<TestCustomInput> for BasicTestSceneLoader {
-    fn load_scene(&self, ecs: Arc<RwLock<World>>, window: &Window) -> Task<Box<dyn Scene<TestCustomInput>>> {
-        let scene_json: SceneLoaderJSON = map_err_return!(
-            from_value(self.scene_json.actual_value.clone()),
-            |e| {
-                build_task_error(
-                    ConvertJSONError {
-                        value: self.scene_json.actual_value.clone(),
-                        into_type: stringify!(SceneLoaderJSON).to_string(),
-                        source: e
-                    },
-                    ErrorKind::InvalidData
-                )
-            }
+impl BasicTestSceneLoader {
+    fn source(&self) -> JSONSource {
+        JSONSource::new(self.scene_path.as_str(), "/actual_value")
+    }
+
+    // Collects every problem in the scene file and its entities rather than stopping at the first.
+    // The assets come back waiting on the window's GPU; --headless drops them and never draws.
+    pub fn load_contents(&self, ecs: Arc<RwLock<World>>) -> Result<(SceneSimulation, AssetUpload)> {
+        let source = self.source();
+        let actual_value = &self.scene_json.actual_value;
+
+        let scene_json: SceneLoaderJSON = convert_json(actual_value, stringify!(SceneLoaderJSON))
+            .map_err(|e| source.locate(Error::new(e)))?;
+
+        let mut scene_entities: SceneEntitiesJSON = convert_json(actual_value, stringify!(SceneEntitiesJSON))
+            .map_err(|e| source.locate(Error::new(e)))?;
+
+        for (index, instance) in scene_entities.entity_instances.iter_mut().enumerate() {
+            instance.source = source.join(format!("/entity_instances/{}", index).as_str());
+        }
+
+        let mut errors = LoadErrors::default();
+
+        let scene_values = errors.collect(
+            convert_json::<BasicTestSceneJSON>(&scene_json.scene_values, stringify!(BasicTestSceneJSON))
+                .map_err(|e| source.join("/scene_values").locate(Error::new(e)))
         );
 
-        let scene_values: BasicTestSceneJSON = map_err_return!(
-            from_value(scene_json.scene_values.clone()),
-            |e| {
-                panic!("Error trying to convert json value into scene values: {}", scene_json.scene_values);
-            });
+        info!(
+            scene = BASIC_TEST_SCENE_FILE_ID,
+            entity_paths = scene_json.entity_paths.len(),
+            entity_instances = scene_entities.entity_instances.len(),
+            "Loading scene entities"
+        );
+
+        let dispatcher = errors.collect(
+            SceneDispatcher::new(&scene_entities.systems, &source.join("/systems"))
+        );
+
+        let mut mut_ecs = ecs.write_world();
+
+        let asset_cache = mut_ecs.entry::<AssetCache>().or_insert_with(AssetCache::default).clone();
+        let assets = errors.collect(
+            AssetCatalog::load()
+                .and_then(|catalog| asset_cache.acquire(&scene_entities.assets, &catalog, &source.join("/assets")))
+        );
+
+        let mut entities = errors.collect(load_entity_vec(&scene_json.entity_paths, &*mut_ecs))
+            .unwrap_or_default();
+        entities.extend(
+            errors.collect(load_entity_instances(&scene_entities.entity_instances, &*mut_ecs))
+                .unwrap_or_default()
+        );
+        // Entities are built lazily; their components have to be in storage before they can be checked.
+        mut_ecs.maintain();
+
+        {
+            let images_source = source.join("/assets/images");
+            let tilemaps = mut_ecs.read_storage::<Tilemap>();
+            for entity in &entities {
+                if let Some(tilemap) = tilemaps.get(*entity) {
+                    errors.collect(
+                        tilemap.check_tilesets(&scene_entities.assets.images).map_err(|e| images_source.locate(e))
+                    );
+                }
+            }
+        }
+
+        // Entities spawned later can't be reported like this, so DrawWorld puts unknown layers in the default one.
+        if let Some(scene_values) = &scene_values {
+            let layers_source = source.join("/scene_values/render_layers");
+            errors.collect(scene_values.render_layers.validate().map_err(|e| layers_source.locate(e)));
+
+            let render_layers = mut_ecs.read_storage::<RenderLayer>();
+            for entity in &entities {
+                if let Some(render_layer) = render_layers.get(*entity) {
+                    errors.collect(
+                        scene_values.render_layers.check(render_layer.layer.as_str())
+                            .map_err(|e| layers_source.locate(e))
+                    );
+                }
+            }
+        }
+
+        match (scene_values, dispatcher, assets) {
+            (Some(scene_values), Some(mut dispatcher), Some(assets)) if errors.is_empty() => {
+                mut_ecs.entry::<GameEvents>().or_insert_with(GameEvents::default);
+                mut_ecs.insert(scene_values.camera.clone());
+                mut_ecs.insert(scene_values.gravity);
+                mut_ecs.insert(scene_values.render_layers.clone());
+                dispatcher.setup(&mut *mut_ecs);
+                Ok((SceneSimulation::new(entities, dispatcher, scene_values), assets))
+            },
+            _ => {
+                for entity in entities {
+                    let _ = mut_ecs.entities().delete(entity);
+                }
+                Err(Error::new(errors))
+            }
+        }
+    }
+}
+
+// JSONLoad isn't Clone, and load_scene moves a copy of the loader into its Task.
+impl Clone for BasicTestSceneLoader {
+    fn clone(&self) -> Self {
+        BasicTestSceneLoader {
+            scene_json: JSONLoad {
+                load_type_id: self.scene_json.load_type_id.clone(),
+                actual_value: self.scene_json.actual_value.clone()
+            },
+            scene_path: self.scene_path.clone()
+        }
+    }
+}
+
+impl SceneLoader<TestCustomInput> for BasicTestSceneLoader {
+    // The scene is built when the Task runs, not when the stack asks for it, so the loading screen
+    // is already up. A Task can't pick what runs next from an earlier result, so the build leaves
+    // its result in `built` for the assets stage to upload, or to turn into a LoadErrorScene.
+    fn load_scene(&self, ecs: Arc<RwLock<World>>, _window: &Window) -> Task<Box<dyn Scene<TestCustomInput>>> {
+        let loader = self.clone();
+        let built: Arc<Mutex<Option<Result<(SceneSimulation, AssetUpload)>>>> = Arc::new(Mutex::new(None));
+        let build_result = built.clone();
+
+        (
+            stage("Building scene", Task::succeed(move || {
+                let contents = loader.load_contents(ecs.clone());
+                *build_result.lock().unwrap_or_else(PoisonError::into_inner) = Some(contents);
+            })),
+            stage("Loading scene assets", Task::using_gpu(move |gpu| {
+                let contents = built.lock().unwrap_or_else(PoisonError::into_inner)
+                    .take()
+                    .unwrap_or_else(|| Err(anyhow!("Scene assets were loaded before the scene was built")));
 
-        load_entity_vec::<BasicTestComponentMux>(&scene_json.entity_paths, ecs, window)
-            .map(move |entity_vec| {
-                Box::new(
-                    BasicTestScene {
-                        entities: entity_vec,
-                        text: scene_values.text,
-                        frame: scene_values.frame,
-                        quit_flag: scene_values.quit_flag
+                match contents {
+                    Ok((simulation, upload)) => Ok(Ok((simulation, upload.upload(gpu)?))),
+                    Err(e) => {
+                        let mut errors = LoadErrors::default();
+                        errors.push(e);
+
+                        let font = Font::from_bytes(gpu, DEBUG_FONT)?;
+                        Ok(Err(LoadErrorScene::with_font(BASIC_TEST_SCENE_FILE_ID, errors, font)))
                     }
-                ) as Box<dyn Scene<TestCustomInput>>
+                }
+            })),
+            Inspector::load(cli::options().debug_overlays),
+            OptionsMenu::load(),
+            DebugOverlay::load()
+        ).join()
+            .map(move |(_, contents, inspector, options_menu, debug_overlay)| {
+                match contents {
+                    Ok((simulation, assets)) => Box::new(
+                        BasicTestScene {
+                            quit_flag: simulation.quit_flag,
+                            simulation,
+                            inspector,
+                            options_menu,
+                            debug_overlay,
+                            assets,
+                            key_bindings: settings::current().key_bindings(),
+                            started: false
+                        }
+                    ) as Box<dyn Scene<TestCustomInput>>,
+                    Err(error_scene) => Box::new(error_scene) as Box<dyn Scene<TestCustomInput>>
+                }
             })
     }
 }
 
 #[derive(Debug)]
 pub struct BasicTestScene {
+    simulation: SceneSimulation,
+    inspector: Inspector,
+    options_menu: OptionsMenu,
+    debug_overlay: DebugOverlay,
+    assets: SceneAssets,
+    key_bindings: HashMap<KeyCode, Action>,
+    started: bool,
+    quit_flag: bool
+}
+
+// Everything a scene needs to tick without a window, which --headless drives on its own.
+#[derive(Debug)]
+pub struct SceneSimulation {
     entities: Vec<Entity>,
+    spawner: EntitySpawner,
+    dispatcher: SceneDispatcher,
     text: String,
     frame: usize,
+    timestep: FixedTimestep,
     quit_flag: bool
 }
 
+// Read alongside SceneLoaderJSON from the same actual_value, which ignores fields it doesn't know.
+#[derive(Deserialize, Debug)]
+struct SceneEntitiesJSON {
+    #[serde(default)]
+    entity_instances: Vec<EntityInstanceJSON>,
+    #[serde(default = "default_systems")]
+    systems: Vec<SystemJSON>,
+    #[serde(default)]
+    assets: SceneAssetsJSON
+}
+
 #[derive(Deserialize, Debug)]
 struct BasicTestSceneJSON {
     text: String,
     frame: usize,
-    quit_flag: bool
+    quit_flag: bool,
+    #[serde(default = "default_tick_rate")]
+    tick_rate: u32,
+    #[serde(default)]
+    camera: Camera,
+    #[serde(default)]
+    gravity: Gravity,
+    #[serde(default)]
+    render_layers: RenderLayers
 }
 
-impl Scene<TestCustomInput> for BasicTestScene {
-    fn update(&mut self, ecs: Arc<RwLock<World>>) -> Result<SceneTransition<TestCustomInput>> {
+fn default_tick_rate() -> u32 {
+    DEFAULT_TICK_RATE
+}
+
+impl SceneSimulation {
+    fn new(entities: Vec<Entity>, dispatcher: SceneDispatcher, scene_values: BasicTestSceneJSON) -> Self {
+        SceneSimulation {
+            entities,
+            spawner: EntitySpawner::default(),
+            dispatcher,
+            text: scene_values.text,
+            frame: scene_values.frame,
+            timestep: FixedTimestep::new(scene_values.tick_rate),
+            quit_flag: scene_values.quit_flag
+        }
+    }
+
+    pub fn total_ticks(&self) -> u64 {
+        self.timestep.total_ticks()
+    }
+
+    // One fixed step of game logic; `frame` counts these ticks, not rendered frames.
+    fn tick(&mut self, ecs: &World) {
         self.frame = self.frame + 1;
+        let _span = info_span!("tick", scene = SCENE_NAME, frame = self.frame).entered();
+
+        RecordPreviousPositions.run_now(ecs);
+        self.dispatcher.dispatch(ecs);
+
+        let tick_rate = self.timestep.tick_rate() as usize;
+
+        if self.frame % tick_rate == 0 {
+            debug!(entity_count = self.entities.len(), "Frame checkpoint");
+        }
+
+        if self.frame == DUMP_AFTER_SECONDS * tick_rate {
+            info!(scene = SCENE_NAME, frame = self.frame, "Entity dump:\n{}", Inspector::dump(ecs));
+        }
+    }
+
+    // Runs however many ticks the wall clock says are due.
+    pub fn update(&mut self, ecs: &mut World) -> Result<()> {
+        let ticks = self.timestep.advance();
+        self.run_ticks(ecs, ticks)
+    }
+
+    // Runs exactly one tick, for --headless, where time is counted in ticks rather than read from a clock.
+    pub fn step(&mut self, ecs: &mut World) -> Result<()> {
+        let ticks = self.timestep.advance_by(self.timestep.tick_duration());
+        self.run_ticks(ecs, ticks)
+    }
 
-        let immut_ecs = ecs.read()
-            .map_err(|e| {
-                anyhow::Error::new(ECSReadError {
-                    source_string: e.to_string()
-                })
-            })?;
+    // The spawn queue is drained after the ticks, so everything queued this update is in the world
+    // before the next draw or interact.
+    fn run_ticks(&mut self, ecs: &mut World, ticks: u32) -> Result<()> {
+        *ecs.fetch_mut::<DeltaTime>() = self.timestep.delta_time();
 
-        let mut animate_sprites = AnimateSprites;
-        animate_sprites.run_now(&*immut_ecs);
+        // Shapes from every tick of this update are kept for the draw, and updates that run no tick
+        // leave the last ones in place rather than drawing nothing.
+        if ticks > 0 {
+            ecs.fetch_mut::<DebugDraw>().clear();
+        }
+
+        for _ in 0..ticks {
+            self.tick(ecs);
+        }
+
+        *ecs.fetch_mut::<Interpolation>() = self.timestep.interpolation();
 
-        let mut play_sounds = PlayDefaultSounds {};
-        play_sounds.run_now(&*immut_ecs);
+        let despawned = EntitySpawner::drain_despawns(ecs)?;
+        for entity in &despawned {
+            info!(scene = SCENE_NAME, frame = self.frame, entity = entity.id(), "Despawned entity");
+        }
+        self.entities.retain(|entity| !despawned.contains(entity));
+        ecs.fetch_mut::<GameEvents>()
+            .iter_write(despawned.iter().map(|entity| GameEvent::EntityDespawned { entity: *entity }));
+
+        let spawned = self.spawner.drain_spawns(ecs);
+        for entity in &spawned {
+            info!(scene = SCENE_NAME, frame = self.frame, entity = entity.id(), "Spawned entity");
+        }
+        ecs.fetch_mut::<GameEvents>()
+            .iter_write(spawned.iter().map(|entity| GameEvent::EntitySpawned { entity: *entity }));
+        self.entities.extend(spawned);
+
+        Ok(())
+    }
+}
+
+impl BasicTestScene {
+    fn update_world(&mut self, ecs: &mut World) -> Result<SceneTransition<TestCustomInput>> {
+        self.simulation.update(ecs)?;
 
         Ok(SceneTransition::NONE)
     }
 
-    fn draw(&mut self, ecs: Arc<RwLock<World>>, frame: &mut Frame, timer: &Timer) -> Result<()> {
+    fn draw_world(&mut self, ecs: &World, frame: &mut Frame) -> Result<()> {
+        let _span = info_span!("draw", scene = %self.get_name(), frame = self.simulation.frame).entered();
         frame.clear(Color::BLACK);
 
-        let immut_ecs = ecs.read()
-            .map_err(|e| {
-                anyhow::Error::new(ECSReadError {
-                    source_string: e.to_string()
-                })
-            })?;
+        let mut draw_world = DrawWorld { frame, assets: &self.assets };
+        draw_world.run_now(ecs);
+
+        self.debug_overlay.draw(ecs, frame);
+
+        if self.inspector.is_visible() {
+            self.inspector.draw(ecs, frame);
+        }
+
+        if self.options_menu.is_visible() {
+            self.options_menu.draw(frame);
+        }
+
+        Ok(())
+    }
+
+    fn interact_world(&mut self, ecs: &mut World, input: &mut TestCustomInput, window: &mut Window) -> Result<()> {
+        if input.keys_pressed.contains(&KeyCode::Q) {
+            self.quit_flag = true;
+        }
+
+        if !self.started {
+            self.started = true;
+            ecs.fetch_mut::<GameEvents>().single_write(GameEvent::SceneChanged {
+                scene_name: self.get_name()
+            });
+        }
+
+        // coffee doesn't hand resize events to scenes, so the window size is polled each interact.
+        let resized = ecs.fetch_mut::<Viewport>().resize(window.width(), window.height());
+        if resized {
+            info!(width = window.width(), height = window.height(), "Window resized");
+            ecs.fetch_mut::<GameEvents>().single_write(GameEvent::WindowResized {
+                width: window.width(),
+                height: window.height()
+            });
+        }
+
+        if input.keys_pressed.contains(&KeyCode::Escape) {
+            self.options_menu.toggle();
+        }
+
+        // The menu takes the arrow keys while it's open, so they don't also move the player.
+        if self.options_menu.is_visible() {
+            self.options_menu.interact(ecs, input, window);
+        } else {
+            ecs.fetch_mut::<GameEvents>().iter_write(
+                input.get_actions(&self.key_bindings).into_iter()
+                    .map(|(action, state)| GameEvent::ActionTriggered { action, state })
+            );
+        }
+
+        {
+            let mut spawn_queue = ecs.fetch_mut::<SpawnQueue>();
+
+            if input.keys_pressed.contains(&KeyCode::S) {
+                spawn_queue.spawn(SpawnRequest::new(SPAWN_PREFAB_ID));
+            }
+
+            if input.keys_pressed.contains(&KeyCode::D) {
+                if let Some(entity) = self.simulation.entities.last() {
+                    spawn_queue.despawn(*entity);
+                }
+            }
+        }
 
-        let mut draw_basic = DrawBasic { frame };
-        draw_basic.run_now(&*immut_ecs);
+        if input.keys_pressed.contains(&KeyCode::F1) {
+            self.inspector.toggle();
+        }
 
+        if input.keys_pressed.contains(&KeyCode::PageDown) {
+            self.inspector.select_next(ecs);
+        }
 
-        if self.frame % 60 == 0 {
-            println!("NUM FRAMES: {}", self.frame);
+        if input.keys_pressed.contains(&KeyCode::PageUp) {
+            self.inspector.select_previous(ecs);
         }
 
-        if self.frame == LAST_FRAME {
-            let mut print_components = PrintBasicComponents;
-            print_components.run_now(&*immut_ecs);
+        if input.keys_pressed.contains(&KeyCode::F3) {
+            ecs.fetch_mut::<DebugDraw>().toggle();
         }
-        return Ok(())
+
+        if input.keys_pressed.contains(&KeyCode::F2) {
+            info!(scene = %self.get_name(), frame = self.simulation.frame, "Entity dump:\n{}", Inspector::dump(ecs));
+        }
+
+        if input.keys_pressed.contains(&KeyCode::F5) {
+            // A bad edit file is a typo to fix and retry, not a reason to stop the game.
+            let edit_path = [LOAD_PATH, JSON_ASSETS_DIR, DEBUG_DIR, INSPECTOR_EDIT_FILE_ID, JSON_FILE].join("");
+            let inspector = &self.inspector;
+            let applied = load_json::<JSONLoad>(edit_path.as_str())
+                .and_then(|edit| inspector.apply_edit(ecs, &edit).map(|_| edit));
+
+            match applied {
+                Ok(edit) => info!(file_path = %edit_path, load_type_id = %edit.load_type_id, "Applied inspector edit"),
+                Err(e) => error!(file_path = %edit_path, "Failed to apply inspector edit: {:#}", e)
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// The engine's Scene trait still hands out the shared lock; each call takes it exactly once and
+// works on a plain World from there.
+impl Scene<TestCustomInput> for BasicTestScene {
+    fn update(&mut self, ecs: Arc<RwLock<World>>) -> Result<SceneTransition<TestCustomInput>> {
+        ecs.with_world_mut(|world| self.update_world(world))
+    }
+
+    fn draw(&mut self, ecs: Arc<RwLock<World>>, frame: &mut Frame, _timer: &Timer) -> Result<()> {
+        ecs.with_world(|world| self.draw_world(world, frame))
     }
 
     fn interact(&mut self, ecs: Arc<RwLock<World>>, input: &mut TestCustomInput, window: &mut Window) -> Result<()> {
-        self.quit_flag = input.keys_pressed.contains(&KeyCode::Q);
-        return Ok(())
+        ecs.with_world_mut(|world| self.interact_world(world, input, window))
     }
 
     fn get_name(&self) -> String {
-        "BASIC TEST SCENE".to_string()
+        SCENE_NAME.to_string()
     }
 
     fn is_finished(&self) -> Result<bool> {
         return Ok(self.quit_flag)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,197 @@
+use coffee::graphics::{Window, Frame, Font, Text, Point, Color, HorizontalAlignment, VerticalAlignment};
+use coffee::{Timer};
+use coffee::load::Task;
+use coffee::input::keyboard::KeyCode;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use anyhow::{Result, Error};
+
+use std::sync::{Arc, RwLock};
+use std::fmt::{Debug, Formatter};
+
+use specs::World;
+
+use game_engine::load::JSONLoad;
+use game_engine::scenes::{Scene, SceneLoader};
+use game_engine::scenes::scene_stack::SceneTransition;
+
+use tracing::error;
+
+use crate::input::TestCustomInput;
+use crate::globals::{DEBUG_FONT, LoadErrors, TempJSON, convert_json, write_temp_json};
+use crate::globals::TestGlobalError::LoadIDMatchError;
+use crate::cli::single_scene_stack;
+
+pub const LOAD_ERROR_SCENE_FILE_ID: &str = "load_error_scene";
+
+const TITLE_SIZE: f32 = 32.0;
+const TEXT_SIZE: f32 = 18.0;
+const LINE_SPACING: f32 = 6.0;
+const MARGIN: f32 = 20.0;
+
+const TITLE_COLOR: Color = Color { r: 1.0, g: 0.3, b: 0.3, a: 1.0 };
+
+// Shown in place of a scene that failed to load, listing every error instead of aborting the game.
+pub struct LoadErrorScene {
+    scene_name: String,
+    errors: Vec<String>,
+    font: Font,
+    quit_flag: bool
+}
+
+impl LoadErrorScene {
+    pub fn load(scene_name: &str, errors: LoadErrors) -> Task<LoadErrorScene> {
+        Self::load_descriptions(scene_name, errors.descriptions())
+    }
+
+    // For load Tasks that already have the GPU to hand, so the error scene needs no Task of its own.
+    pub fn with_font(scene_name: &str, errors: LoadErrors, font: Font) -> LoadErrorScene {
+        Self::from_descriptions(scene_name.to_string(), errors.descriptions(), font)
+    }
+
+    fn load_descriptions(scene_name: &str, errors: Vec<String>) -> Task<LoadErrorScene> {
+        let scene_name = scene_name.to_string();
+
+        Font::load_from_bytes(DEBUG_FONT)
+            .map(move |font| Self::from_descriptions(scene_name, errors, font))
+    }
+
+    fn from_descriptions(scene_name: String, errors: Vec<String>, font: Font) -> LoadErrorScene {
+        for description in &errors {
+            error!(scene = %scene_name, "{}", description);
+        }
+
+        LoadErrorScene {
+            scene_name,
+            errors,
+            font,
+            quit_flag: false
+        }
+    }
+}
+
+impl Debug for LoadErrorScene {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoadErrorScene")
+            .field("scene_name", &self.scene_name)
+            .field("errors", &self.errors)
+            .field("quit_flag", &self.quit_flag)
+            .finish()
+    }
+}
+
+impl Scene<TestCustomInput> for LoadErrorScene {
+    fn update(&mut self, _ecs: Arc<RwLock<World>>) -> Result<SceneTransition<TestCustomInput>> {
+        Ok(SceneTransition::NONE)
+    }
+
+    fn draw(&mut self, _ecs: Arc<RwLock<World>>, frame: &mut Frame, _timer: &Timer) -> Result<()> {
+        frame.clear(Color::BLACK);
+
+        let bounds = (frame.width() - MARGIN * 2.0, frame.height());
+        let title = format!("Failed to load {} ({} errors). Press Q to quit.", self.scene_name, self.errors.len());
+
+        self.font.add(Text {
+            content: title.as_str(),
+            position: Point::new(MARGIN, MARGIN),
+            bounds,
+            size: TITLE_SIZE,
+            color: TITLE_COLOR,
+            horizontal_alignment: HorizontalAlignment::Left,
+            vertical_alignment: VerticalAlignment::Top
+        });
+
+        let mut y = MARGIN * 2.0 + TITLE_SIZE;
+        for description in &self.errors {
+            self.font.add(Text {
+                content: description.as_str(),
+                position: Point::new(MARGIN, y),
+                bounds,
+                size: TEXT_SIZE,
+                color: Color::WHITE,
+                horizontal_alignment: HorizontalAlignment::Left,
+                vertical_alignment: VerticalAlignment::Top
+            });
+
+            // Long errors wrap, so leave room for roughly how many lines they will take.
+            let wrapped_lines = (description.len() as f32 * TEXT_SIZE * 0.5 / bounds.0).ceil().max(1.0);
+            y += wrapped_lines * (TEXT_SIZE + LINE_SPACING);
+        }
+
+        self.font.draw(&mut frame.as_target());
+
+        Ok(())
+    }
+
+    fn interact(&mut self, _ecs: Arc<RwLock<World>>, input: &mut TestCustomInput, _window: &mut Window) -> Result<()> {
+        self.quit_flag = input.keys_pressed.contains(&KeyCode::Q)
+            || input.keys_pressed.contains(&KeyCode::Escape);
+
+        Ok(())
+    }
+
+    fn get_name(&self) -> String {
+        "LOAD ERROR SCENE".to_string()
+    }
+
+    fn is_finished(&self) -> Result<bool> {
+        Ok(self.quit_flag)
+    }
+}
+
+// The engine only builds scene stacks from files, so errors that stop a stack from loading at all
+// are written to a scene file of their own and shown through this loader.
+#[derive(Deserialize, Serialize, Debug)]
+struct LoadErrorSceneJSON {
+    scene_name: String,
+    errors: Vec<String>
+}
+
+#[derive(Debug)]
+pub struct LoadErrorSceneLoader {
+    scene_json: LoadErrorSceneJSON
+}
+
+impl LoadErrorSceneLoader {
+    pub fn new(json: JSONLoad) -> Result<Self> {
+        if json.load_type_id == LOAD_ERROR_SCENE_FILE_ID {
+            Ok(LoadErrorSceneLoader {
+                scene_json: convert_json(&json.actual_value, stringify!(LoadErrorSceneJSON))?
+            })
+        } else {
+            Err(Error::new(LoadIDMatchError {
+                expected_id: LOAD_ERROR_SCENE_FILE_ID.to_string(),
+                actual_id: json.load_type_id
+            }))
+        }
+    }
+
+    pub fn from_errors(scene_name: &str, errors: &LoadErrors) -> Self {
+        LoadErrorSceneLoader {
+            scene_json: LoadErrorSceneJSON {
+                scene_name: scene_name.to_string(),
+                errors: errors.descriptions()
+            }
+        }
+    }
+}
+
+impl SceneLoader<TestCustomInput> for LoadErrorSceneLoader {
+    fn load_scene(&self, _ecs: Arc<RwLock<World>>, _window: &Window) -> Task<Box<dyn Scene<TestCustomInput>>> {
+        LoadErrorScene::load_descriptions(self.scene_json.scene_name.as_str(), self.scene_json.errors.clone())
+            .map(|scene| Box::new(scene) as Box<dyn Scene<TestCustomInput>>)
+    }
+}
+
+// Writes a scene stack holding only an error scene for `scene_name`, to load in place of one that can't.
+// Returns the stack, then the scene file it points at; both have to outlive loading the stack.
+pub fn error_scene_stack(scene_name: &str, errors: &LoadErrors) -> Result<(TempJSON, TempJSON)> {
+    let scene = write_temp_json(LOAD_ERROR_SCENE_FILE_ID, &json!({
+        "load_type_id": LOAD_ERROR_SCENE_FILE_ID,
+        "actual_value": LoadErrorSceneLoader::from_errors(scene_name, errors).scene_json
+    }))?;
+
+    Ok((single_scene_stack(scene.path().as_str())?, scene))
+}
@@ -3,13 +3,57 @@ use anyhow::{Result, Error};
 use game_engine::load::JSONLoad;
 use game_engine::scenes::SceneLoader;
 use crate::scenes::basic_test_scene::{BasicTestSceneLoader, BASIC_TEST_SCENE_FILE_ID};
+use crate::scenes::load_error_scene::{LoadErrorSceneLoader, LOAD_ERROR_SCENE_FILE_ID};
 use crate::globals::TestGlobalError::LoadIDMatchError;
+use crate::globals::{load_json, LoadErrors};
+use serde::Deserialize;
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
 
 pub mod basic_test_scene;
+pub mod load_error_scene;
 
 pub const SCENES_DIR: &str = "scenes/";
 pub const SCENE_STACKS_DIR: &str = "scene_stacks/";
 
+// The engine's SceneStackLoader reads each scene file itself and only hands the factory its JSON,
+// so the stack's scene paths are kept here to find which file a scene came from.
+static SCENE_PATHS: Lazy<Mutex<Vec<String>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+#[derive(Deserialize, Debug)]
+struct SceneStackPathsJSON {
+    scene_paths: Vec<String>
+}
+
+// Returns the stack's scene paths in order. Each scene file is read once here too, since the
+// engine's SceneStackLoader stops the whole game on one it can't read.
+pub fn register_scene_stack(stack_path: &str) -> Result<Vec<String>> {
+    let stack: JSONLoad = load_json(stack_path)?;
+    let paths: SceneStackPathsJSON = serde_json::from_value(stack.actual_value)?;
+
+    if let Ok(mut scene_paths) = SCENE_PATHS.lock() {
+        *scene_paths = paths.scene_paths.clone();
+    }
+
+    let mut errors = LoadErrors::default();
+    for scene_path in &paths.scene_paths {
+        errors.collect(load_json::<JSONLoad>(scene_path.as_str()));
+    }
+
+    errors.into_result(paths.scene_paths)
+}
+
+// The registered path whose file holds exactly this scene JSON, if any.
+pub fn scene_path(json: &JSONLoad) -> Option<String> {
+    let scene_paths = SCENE_PATHS.lock().ok()?.clone();
+
+    scene_paths.into_iter().find(|path| {
+        load_json::<JSONLoad>(path.as_str())
+            .map(|file| file.load_type_id == json.load_type_id && file.actual_value == json.actual_value)
+            .unwrap_or(false)
+    })
+}
+
 pub fn scene_factory(json: JSONLoad) -> Result<Box<dyn SceneLoader<TestCustomInput>>> {
     return match json.load_type_id.as_str() {
         BASIC_TEST_SCENE_FILE_ID => {
@@ -17,9 +61,22 @@ pub fn scene_factory(json: JSONLoad) -> Result<Box<dyn SceneLoader<TestCustomInp
                 BasicTestSceneLoader::new(json)?
             ))
         }
-        _ => Err(Error::new(LoadIDMatchError {
-            expected_id: format!("One of: {:?}", vec!(BASIC_TEST_SCENE_FILE_ID)),
-            actual_id: json.load_type_id
-        }))
+        LOAD_ERROR_SCENE_FILE_ID => {
+            Ok(Box::new(
+                LoadErrorSceneLoader::new(json)?
+            ))
+        }
+        // Shown as an error scene rather than failing the stack, which would stop the game.
+        _ => {
+            let mut errors = LoadErrors::default();
+            errors.push(Error::new(LoadIDMatchError {
+                expected_id: format!("One of: {:?}", vec!(BASIC_TEST_SCENE_FILE_ID)),
+                actual_id: json.load_type_id.clone()
+            }));
+
+            Ok(Box::new(
+                LoadErrorSceneLoader::from_errors(json.load_type_id.as_str(), &errors)
+            ))
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,234 @@
+use coffee::graphics::WindowSettings;
+
+use serde::{Deserialize, Serialize};
+
+use anyhow::Result;
+use once_cell::sync::OnceCell;
+
+use std::collections::HashMap;
+use std::fs::write;
+use std::path::Path;
+use std::sync::{RwLock, PoisonError};
+
+use coffee::input::keyboard::KeyCode;
+
+use tracing::{info, error};
+
+use crate::cli::Cli;
+use crate::globals::{load_json, JSONSource};
+use crate::input::{Action, KeyBindingNames, default_key_bindings, key_binding_names, key_bindings_from_names};
+use crate::rendering::viewport::Viewport;
+
+pub const SETTINGS_FILE: &str = "settings.json";
+pub const DEFAULT_SCENE_STACK: &str = "basic_test_scene_stack";
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    pub window: WindowJSON,
+    pub display: Viewport,
+    pub audio: AudioSettings,
+    pub key_bindings: KeyBindingNames,
+    pub starting_scene_stack: String
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct WindowJSON {
+    pub title: String,
+    pub size: [u32; 2],
+    pub resizable: bool,
+    pub fullscreen: bool,
+    pub maximized: bool,
+    // coffee 0.4's WindowSettings has no vsync switch, so this is saved but not applied yet.
+    pub vsync: bool
+}
+
+// Volumes are 0 to 1. Inserted into the world, where PlayDefaultSounds applies them to the AudioPlayer.
+#[derive(Deserialize, Serialize, Debug, Copy, Clone, PartialEq)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master_volume: f64,
+    pub music_volume: f64,
+    pub sfx_volume: f64
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            window: WindowJSON::default(),
+            display: Viewport::default(),
+            audio: AudioSettings::default(),
+            key_bindings: key_binding_names(&default_key_bindings()),
+            starting_scene_stack: DEFAULT_SCENE_STACK.to_string()
+        }
+    }
+}
+
+impl Default for WindowJSON {
+    fn default() -> Self {
+        WindowJSON {
+            title: String::from("HELLO WORLD"),
+            size: [1280, 1024],
+            resizable: true,
+            fullscreen: false,
+            maximized: false,
+            vsync: true
+        }
+    }
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        AudioSettings {
+            master_volume: 1.0,
+            music_volume: 1.0,
+            sfx_volume: 1.0
+        }
+    }
+}
+
+impl AudioSettings {
+    // A hand-edited file can hold anything, so volumes are pulled back into 0 to 1 on load.
+    fn clamped(self) -> Self {
+        AudioSettings {
+            master_volume: clamp_volume(self.master_volume),
+            music_volume: clamp_volume(self.music_volume),
+            sfx_volume: clamp_volume(self.sfx_volume)
+        }
+    }
+
+    pub fn music(&self) -> f64 {
+        self.master_volume * self.music_volume
+    }
+
+    pub fn sfx(&self) -> f64 {
+        self.master_volume * self.sfx_volume
+    }
+}
+
+impl Settings {
+    // Unknown key names are rejected here so a typo can't silently unbind an action mid-game.
+    pub fn load(file_path: &str) -> Result<Self> {
+        let mut settings: Settings = load_json(file_path)?;
+        settings.audio = settings.audio.clamped();
+
+        key_bindings_from_names(&settings.key_bindings)
+            .map_err(|e| JSONSource::new(file_path, "/key_bindings").locate(e))?;
+
+        Ok(settings)
+    }
+
+    pub fn save(&self, file_path: &str) -> Result<()> {
+        write(file_path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn apply_cli(&mut self, cli: &Cli) {
+        if let Some(title) = &cli.title {
+            self.window.title = title.clone();
+        }
+        if let Some(width) = cli.width {
+            self.window.size[0] = width;
+        }
+        if let Some(height) = cli.height {
+            self.window.size[1] = height;
+        }
+        if cli.fullscreen {
+            self.window.fullscreen = true;
+        }
+        if cli.windowed {
+            self.window.fullscreen = false;
+        }
+        if let Some(scene_stack) = &cli.scene_stack {
+            self.starting_scene_stack = scene_stack.clone();
+        }
+        if let Some(volume) = cli.master_volume {
+            self.audio.master_volume = clamp_volume(volume);
+        }
+        if let Some(volume) = cli.music_volume {
+            self.audio.music_volume = clamp_volume(volume);
+        }
+        if let Some(volume) = cli.sfx_volume {
+            self.audio.sfx_volume = clamp_volume(volume);
+        }
+    }
+
+    pub fn window_settings(&self) -> WindowSettings {
+        WindowSettings {
+            title: self.window.title.clone(),
+            size: (self.window.size[0], self.window.size[1]),
+            resizable: self.window.resizable,
+            fullscreen: self.window.fullscreen,
+            maximized: self.window.maximized
+        }
+    }
+
+    // Already validated by load, so anything unparseable here falls back to the defaults.
+    pub fn key_bindings(&self) -> HashMap<KeyCode, Action> {
+        key_bindings_from_names(&self.key_bindings).unwrap_or_else(|_| default_key_bindings())
+    }
+}
+
+// CLI overrides apply to `current` only, so saving from the options menu never bakes them into the file.
+struct SettingsStore {
+    file_path: String,
+    file_settings: Settings,
+    current: Settings
+}
+
+static SETTINGS: OnceCell<RwLock<SettingsStore>> = OnceCell::new();
+
+// A missing file is normal on first run; a broken one is logged and replaced by defaults in memory only.
+pub fn init(cli: &Cli, file_path: String) -> Settings {
+
+    let file_settings = if Path::new(file_path.as_str()).exists() {
+        Settings::load(file_path.as_str()).unwrap_or_else(|e| {
+            error!("Using default settings: {:#}", e);
+            Settings::default()
+        })
+    } else {
+        info!(file_path = %file_path, "No settings file, using defaults");
+        Settings::default()
+    };
+
+    let mut current = file_settings.clone();
+    current.apply_cli(cli);
+
+    let _ = SETTINGS.set(RwLock::new(SettingsStore {
+        file_path,
+        file_settings,
+        current: current.clone()
+    }));
+
+    current
+}
+
+pub fn current() -> Settings {
+    match SETTINGS.get() {
+        Some(store) => store.read().unwrap_or_else(PoisonError::into_inner).current.clone(),
+        None => Settings::default()
+    }
+}
+
+pub fn clamp_volume(volume: f64) -> f64 {
+    volume.max(0.0).min(1.0)
+}
+
+// Applies a change to both the running and the saved settings, then writes the file. `change` only
+// sets values; anything relative is worked out from current() first, since the saved settings lack
+// the CLI overrides and would otherwise drift from what's running.
+pub fn update<F: Fn(&mut Settings)>(change: F) -> Result<Settings> {
+    let store = SETTINGS.get_or_init(|| RwLock::new(SettingsStore {
+        file_path: SETTINGS_FILE.to_string(),
+        file_settings: Settings::default(),
+        current: Settings::default()
+    }));
+    let mut store = store.write().unwrap_or_else(PoisonError::into_inner);
+
+    change(&mut store.current);
+    change(&mut store.file_settings);
+    store.file_settings.save(store.file_path.as_str())?;
+
+    Ok(store.current.clone())
+}
@@ -1,26 +1,23 @@
 use specs::prelude::*;
 
-use coffee::graphics::Frame;
-
 use crate::components::animation::Animation;
-use crate::components::position::Position;
-use std::borrow::BorrowMut;
+use crate::components::drawable::Drawable;
 
-pub struct AnimationPlayer<'a, 'b> {
-    pub frame: &'a mut Frame<'b>,
-}
+// Steps animation frames once per fixed tick; DrawWorld picks the current frame out of the atlas
+// (or, for a Drawable's strips, out of the strip's image).
+pub struct AnimationPlayer;
 
-impl<'a> System<'a> for AnimationPlayer<'_, '_> {
-    type SystemData = (
-            WriteStorage<'a, Animation>,
-            WriteStorage<'a, Position>,
-        );
+impl<'a> System<'a> for AnimationPlayer {
+    type SystemData = (WriteStorage<'a, Animation>, WriteStorage<'a, Drawable>);
 
-    fn run(&mut self, (mut animation, mut position): Self::SystemData) {
-        for (an, pos) in (&mut animation, &mut position).join() {
-            let sprite = an.create_sprite(pos);
-            let graphic = an.image.clone();
-            graphic.draw(sprite, self.frame.as_target().borrow_mut());
+    fn run(&mut self, (mut animations, mut drawables): Self::SystemData) {
+        for animation in (&mut animations).join() {
+            animation.advance();
+        }
+        for drawable in (&mut drawables).join() {
+            for sprite in drawable.sprites.iter_mut() {
+                sprite.advance();
+            }
         }
     }
 }
@@ -0,0 +1,135 @@
+use crate::components::{collider::Collider, player_control::PlayerControl, position::Position, velocity::Velocity, rigid_body::RigidBody};
+use crate::collision::{contact, Bounds, SpatialHash};
+use crate::events::{GameEvent, GameEvents};
+use crate::physics::reflect;
+use crate::rendering::debug_draw::DebugDraw;
+
+use coffee::graphics::{Color, Rectangle};
+
+use specs::prelude::*;
+
+// Roughly the size of the colliders in the test game; anything much bigger spans a few cells.
+const CELL_SIZE: f32 = 64.0;
+
+const SOLID_COLOR: Color = Color { r: 0.0, g: 1.0, b: 0.0, a: 1.0 };
+const TRIGGER_COLOR: Color = Color { r: 1.0, g: 1.0, b: 0.0, a: 1.0 };
+const TOUCHING_COLOR: Color = Color { r: 1.0, g: 0.0, b: 0.0, a: 1.0 };
+
+struct Body {
+    entity: Entity,
+    collider: Collider,
+    bounds: Bounds,
+    dynamic: bool
+}
+
+// Reports every overlapping pair as a Collision event each tick, and pushes PlayerControl and
+// RigidBody entities out of the solid colliders they overlap, bouncing their Velocity off them.
+pub struct DetectCollisions;
+
+impl<'a> System<'a> for DetectCollisions {
+    type SystemData = (
+        Entities<'a>,
+        Write<'a, GameEvents>,
+        Write<'a, DebugDraw>,
+        ReadStorage<'a, Collider>,
+        ReadStorage<'a, PlayerControl>,
+        ReadStorage<'a, RigidBody>,
+        WriteStorage<'a, Velocity>,
+        WriteStorage<'a, Position>,
+    );
+
+    fn run(&mut self, (entities, mut events, mut debug_draw, colliders, p, rigid_bodies, mut vel, mut pos): Self::SystemData) {
+        let mut bodies: Vec<Body> = (&entities, &colliders, &pos, p.maybe(), rigid_bodies.maybe()).join()
+            .map(|(entity, collider, pos, controlled, rigid_body)| Body {
+                entity,
+                collider: *collider,
+                bounds: collider.bounds(pos),
+                dynamic: controlled.is_some() || rigid_body.is_some()
+            })
+            .collect();
+
+        let mut hash = SpatialHash::new(CELL_SIZE);
+        for (index, body) in bodies.iter().enumerate() {
+            hash.insert(index, &body.bounds);
+        }
+
+        let mut touching = vec![false; bodies.len()];
+        for (first, second) in hash.candidate_pairs() {
+            if !bodies[first].collider.interacts_with(&bodies[second].collider) {
+                continue;
+            }
+
+            let hit = match contact(&bodies[first].bounds, &bodies[second].bounds) {
+                Some(hit) => hit,
+                None => continue
+            };
+
+            touching[first] = true;
+            touching[second] = true;
+
+            let trigger = bodies[first].collider.trigger || bodies[second].collider.trigger;
+            events.single_write(GameEvent::Collision {
+                first: bodies[first].entity,
+                second: bodies[second].entity,
+                trigger
+            });
+
+            if trigger {
+                continue;
+            }
+
+            // Two dynamic bodies split the push between them; otherwise the dynamic one takes all of it.
+            let (first_share, second_share) = match (bodies[first].dynamic, bodies[second].dynamic) {
+                (true, true) => (0.5, 0.5),
+                (true, false) => (1.0, 0.0),
+                (false, true) => (0.0, 1.0),
+                (false, false) => continue
+            };
+
+            for (index, share) in [(first, first_share), (second, -second_share)].iter() {
+                if *share == 0.0 {
+                    continue;
+                }
+
+                let entity = bodies[*index].entity;
+                let push = [hit.normal[0] * hit.depth * share, hit.normal[1] * hit.depth * share];
+                bodies[*index].bounds.translate(push);
+                if let Some(pos) = pos.get_mut(entity) {
+                    pos.x += push[0];
+                    pos.y += push[1];
+                }
+
+                if let Some(vel) = vel.get_mut(entity) {
+                    let normal = [hit.normal[0] * share.signum(), hit.normal[1] * share.signum()];
+                    let (restitution, friction) = rigid_bodies.get(entity)
+                        .map_or((0.0, 0.0), |body| (body.restitution, body.friction));
+                    let bounced = reflect([vel.x, vel.y], normal, restitution, friction);
+                    vel.x = bounced[0];
+                    vel.y = bounced[1];
+                }
+            }
+        }
+
+        if debug_draw.is_enabled() {
+            for (body, touching) in bodies.iter().zip(touching) {
+                let color = if touching {
+                    TOUCHING_COLOR
+                } else if body.collider.trigger {
+                    TRIGGER_COLOR
+                } else {
+                    SOLID_COLOR
+                };
+
+                match body.bounds {
+                    Bounds::Aabb { center, half_extents } => debug_draw.rect(Rectangle {
+                        x: center[0] - half_extents[0],
+                        y: center[1] - half_extents[1],
+                        width: half_extents[0] * 2.0,
+                        height: half_extents[1] * 2.0
+                    }, color),
+                    Bounds::Circle { center, radius } => debug_draw.circle(center, radius, color)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::components::{player_control::PlayerControl, position::Position};
+use crate::rendering::camera::Camera;
+use crate::timestep::DeltaTime;
+
+use specs::prelude::*;
+
+pub struct FollowCamera;
+
+impl<'a> System<'a> for FollowCamera {
+    type SystemData = (
+        Write<'a, Camera>,
+        Read<'a, DeltaTime>,
+        ReadStorage<'a, PlayerControl>,
+        ReadStorage<'a, Position>,
+    );
+
+    fn run(&mut self, (mut camera, delta_time, p, pos): Self::SystemData) {
+        if let Some((_, pos)) = (&p, &pos).join().next() {
+            camera.follow_target([pos.x, pos.y], delta_time.0);
+        }
+    }
+}
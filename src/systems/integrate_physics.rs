@@ -0,0 +1,40 @@
+use crate::components::{position::Position, velocity::Velocity, rigid_body::RigidBody};
+use crate::physics::Gravity;
+use crate::timestep::DeltaTime;
+
+use specs::prelude::*;
+
+// Semi-implicit Euler: velocity is updated first, then moves the entity, which keeps stacked
+// bodies steady at a fixed timestep. Entities with Velocity but no RigidBody just drift.
+pub struct IntegratePhysics;
+
+impl<'a> System<'a> for IntegratePhysics {
+    type SystemData = (
+        Read<'a, DeltaTime>,
+        Read<'a, Gravity>,
+        WriteStorage<'a, RigidBody>,
+        WriteStorage<'a, Velocity>,
+        WriteStorage<'a, Position>,
+    );
+
+    fn run(&mut self, (delta_time, gravity, mut bodies, mut velocities, mut pos): Self::SystemData) {
+        let dt = delta_time.0;
+
+        for (body, velocity) in (&mut bodies, &mut velocities).join() {
+            let acceleration = body.acceleration(gravity.0);
+            velocity.x += acceleration[0] * dt;
+            velocity.y += acceleration[1] * dt;
+
+            let damping = 1.0 / (1.0 + body.drag * dt);
+            velocity.x *= damping;
+            velocity.y *= damping;
+
+            body.force = [0.0, 0.0];
+        }
+
+        for (velocity, pos) in (&velocities, &mut pos).join() {
+            pos.x += velocity.x * dt;
+            pos.y += velocity.y * dt;
+        }
+    }
+}
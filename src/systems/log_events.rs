@@ -0,0 +1,28 @@
+use crate::events::{GameEvent, GameEvents};
+
+use specs::prelude::*;
+use specs::shrev::ReaderId;
+
+use tracing::debug;
+
+#[derive(Default)]
+pub struct LogEvents {
+    reader: Option<ReaderId<GameEvent>>
+}
+
+impl<'a> System<'a> for LogEvents {
+    type SystemData = Read<'a, GameEvents>;
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader = Some(world.fetch_mut::<GameEvents>().register_reader());
+    }
+
+    fn run(&mut self, events: Self::SystemData) {
+        if let Some(reader) = self.reader.as_mut() {
+            for event in events.read(reader) {
+                debug!(event = ?event, "Game event");
+            }
+        }
+    }
+}
@@ -1 +1,217 @@
-pub mod print_basic_components;
+pub mod move_player;
+pub mod log_events;
+pub mod follow_camera;
+pub mod animation_player;
+pub mod spawn_tilemap_objects;
+pub mod detect_collisions;
+pub mod integrate_physics;
+pub mod update_particles;
+pub mod play_tweens;
+pub mod propagate_transforms;
+pub mod play_default_sounds;
+
+use specs::{Dispatcher, DispatcherBuilder, World};
+
+use serde::Deserialize;
+
+use anyhow::Result;
+use thiserror::Error;
+
+use std::fmt::{Debug, Formatter};
+
+use game_engine::systems::animate_sprites::AnimateSprites;
+
+use crate::globals::{JSONSource, LoadErrors};
+use crate::systems::move_player::MovePlayer;
+use crate::systems::log_events::LogEvents;
+use crate::systems::follow_camera::FollowCamera;
+use crate::systems::animation_player::AnimationPlayer;
+use crate::systems::spawn_tilemap_objects::SpawnTilemapObjects;
+use crate::systems::detect_collisions::DetectCollisions;
+use crate::systems::integrate_physics::IntegratePhysics;
+use crate::systems::update_particles::UpdateParticles;
+use crate::systems::play_tweens::PlayTweens;
+use crate::systems::propagate_transforms::PropagateTransforms;
+use crate::systems::play_default_sounds::PlayDefaultSounds;
+use crate::systems::SystemError::{UnknownSystemError, UnknownDependencyError, DuplicateSystemError};
+
+pub const ANIMATE_SPRITES_SYSTEM_ID: &str = "animate_sprites";
+pub const PLAY_DEFAULT_SOUNDS_SYSTEM_ID: &str = "play_default_sounds";
+pub const MOVE_PLAYER_SYSTEM_ID: &str = "move_player";
+pub const LOG_EVENTS_SYSTEM_ID: &str = "log_events";
+pub const FOLLOW_CAMERA_SYSTEM_ID: &str = "follow_camera";
+pub const ANIMATION_PLAYER_SYSTEM_ID: &str = "animation_player";
+pub const SPAWN_TILEMAP_OBJECTS_SYSTEM_ID: &str = "spawn_tilemap_objects";
+pub const DETECT_COLLISIONS_SYSTEM_ID: &str = "detect_collisions";
+pub const INTEGRATE_PHYSICS_SYSTEM_ID: &str = "integrate_physics";
+pub const UPDATE_PARTICLES_SYSTEM_ID: &str = "update_particles";
+pub const PLAY_TWEENS_SYSTEM_ID: &str = "play_tweens";
+pub const PROPAGATE_TRANSFORMS_SYSTEM_ID: &str = "propagate_transforms";
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SystemJSON {
+    pub name: String,
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    #[serde(default)]
+    pub thread_local: bool
+}
+
+impl SystemJSON {
+    fn new(name: &str, dependencies: &[&str], thread_local: bool) -> Self {
+        SystemJSON {
+            name: name.to_string(),
+            dependencies: dependencies.iter().map(|dependency| dependency.to_string()).collect(),
+            thread_local
+        }
+    }
+}
+
+// Used when a scene file doesn't list its own systems.
+pub fn default_systems() -> Vec<SystemJSON> {
+    vec![
+        SystemJSON::new(ANIMATE_SPRITES_SYSTEM_ID, &[], false),
+        SystemJSON::new(ANIMATION_PLAYER_SYSTEM_ID, &[], false),
+        SystemJSON::new(SPAWN_TILEMAP_OBJECTS_SYSTEM_ID, &[], false),
+        SystemJSON::new(MOVE_PLAYER_SYSTEM_ID, &[], false),
+        SystemJSON::new(INTEGRATE_PHYSICS_SYSTEM_ID, &[MOVE_PLAYER_SYSTEM_ID], false),
+        SystemJSON::new(PLAY_TWEENS_SYSTEM_ID, &[INTEGRATE_PHYSICS_SYSTEM_ID], false),
+        // Children are placed before collisions and the camera see them, not a tick late.
+        SystemJSON::new(PROPAGATE_TRANSFORMS_SYSTEM_ID, &[PLAY_TWEENS_SYSTEM_ID], false),
+        SystemJSON::new(DETECT_COLLISIONS_SYSTEM_ID, &[PROPAGATE_TRANSFORMS_SYSTEM_ID], false),
+        SystemJSON::new(FOLLOW_CAMERA_SYSTEM_ID, &[DETECT_COLLISIONS_SYSTEM_ID], false),
+        SystemJSON::new(UPDATE_PARTICLES_SYSTEM_ID, &[DETECT_COLLISIONS_SYSTEM_ID], false),
+        SystemJSON::new(PLAY_DEFAULT_SOUNDS_SYSTEM_ID, &[], true)
+    ]
+}
+
+// Update systems only: draw systems borrow the frame for a single call, so scenes still run them with run_now.
+pub struct SceneDispatcher {
+    dispatcher: Dispatcher<'static, 'static>,
+    system_names: Vec<String>
+}
+
+impl SceneDispatcher {
+    pub fn new(systems: &Vec<SystemJSON>, source: &JSONSource) -> Result<Self> {
+        let mut builder = DispatcherBuilder::new();
+        let mut system_names: Vec<String> = Vec::new();
+        let mut parallel_names: Vec<String> = Vec::new();
+        let mut errors = LoadErrors::default();
+
+        for (index, system) in systems.iter().enumerate() {
+            let system_source = source.join(format!("/{}", index).as_str());
+
+            if system_names.contains(&system.name) {
+                errors.push(system_source.join("/name").locate(anyhow::Error::new(
+                    DuplicateSystemError {
+                        name: system.name.clone()
+                    }
+                )));
+                continue;
+            }
+
+            // specs panics on unknown dependencies, and thread-local systems can't be depended on, so check up front.
+            let unknown_dependency = system.dependencies.iter()
+                .find(|dependency| !parallel_names.contains(dependency));
+            if let Some(dependency) = unknown_dependency {
+                errors.push(system_source.join("/dependencies").locate(anyhow::Error::new(
+                    UnknownDependencyError {
+                        system: system.name.clone(),
+                        dependency: dependency.clone()
+                    }
+                )));
+                continue;
+            }
+
+            match add_system(builder, system) {
+                Ok(next_builder) => {
+                    builder = next_builder;
+                    system_names.push(system.name.clone());
+
+                    if !system.thread_local {
+                        parallel_names.push(system.name.clone());
+                    }
+                },
+                Err(returned_builder) => {
+                    builder = returned_builder;
+                    errors.push(system_source.join("/name").locate(anyhow::Error::new(
+                        UnknownSystemError {
+                            name: system.name.clone()
+                        }
+                    )));
+                }
+            }
+        }
+
+        errors.into_result(SceneDispatcher {
+            dispatcher: builder.build(),
+            system_names
+        })
+    }
+
+    pub fn setup(&mut self, ecs: &mut World) {
+        self.dispatcher.setup(ecs);
+    }
+
+    pub fn dispatch(&mut self, ecs: &World) {
+        self.dispatcher.dispatch(ecs);
+    }
+}
+
+impl Debug for SceneDispatcher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SceneDispatcher")
+            .field("system_names", &self.system_names)
+            .finish()
+    }
+}
+
+// Hands the builder back on an unknown name so the remaining systems can still be checked.
+fn add_system(builder: DispatcherBuilder<'static, 'static>, system: &SystemJSON)
+    -> std::result::Result<DispatcherBuilder<'static, 'static>, DispatcherBuilder<'static, 'static>> {
+    let name = system.name.as_str();
+    let dependencies: Vec<&str> = system.dependencies.iter().map(String::as_str).collect();
+
+    macro_rules! with_system {
+        ($system:expr) => {
+            if system.thread_local {
+                Ok(builder.with_thread_local($system))
+            } else {
+                Ok(builder.with($system, name, dependencies.as_slice()))
+            }
+        };
+    }
+
+    match name {
+        ANIMATE_SPRITES_SYSTEM_ID => with_system!(AnimateSprites),
+        PLAY_DEFAULT_SOUNDS_SYSTEM_ID => with_system!(PlayDefaultSounds),
+        MOVE_PLAYER_SYSTEM_ID => with_system!(MovePlayer::default()),
+        LOG_EVENTS_SYSTEM_ID => with_system!(LogEvents::default()),
+        FOLLOW_CAMERA_SYSTEM_ID => with_system!(FollowCamera),
+        ANIMATION_PLAYER_SYSTEM_ID => with_system!(AnimationPlayer),
+        SPAWN_TILEMAP_OBJECTS_SYSTEM_ID => with_system!(SpawnTilemapObjects),
+        DETECT_COLLISIONS_SYSTEM_ID => with_system!(DetectCollisions),
+        INTEGRATE_PHYSICS_SYSTEM_ID => with_system!(IntegratePhysics),
+        UPDATE_PARTICLES_SYSTEM_ID => with_system!(UpdateParticles),
+        PLAY_TWEENS_SYSTEM_ID => with_system!(PlayTweens),
+        PROPAGATE_TRANSFORMS_SYSTEM_ID => with_system!(PropagateTransforms),
+        _ => Err(builder)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum SystemError {
+    #[error("No system registered with name: {name}")]
+    UnknownSystemError {
+        name: String
+    },
+    #[error("System {name} is listed more than once")]
+    DuplicateSystemError {
+        name: String
+    },
+    #[error("System {system} depends on {dependency}, which isn't listed before it")]
+    UnknownDependencyError {
+        system: String,
+        dependency: String
+    }
+}
@@ -1,52 +1,105 @@
-use crate::components::{player_control::PlayerControl, position::Position};
+use crate::components::{player_control::PlayerControl, position::Position, velocity::Velocity, rigid_body::RigidBody};
+use crate::physics::Gravity;
+use crate::timestep::DeltaTime;
+use crate::events::{GameEvent, GameEvents};
+use crate::input::{Action, ActionState};
 
 use specs::prelude::*;
-
-use coffee::input::keyboard::KeyCode;
+use specs::shrev::ReaderId;
 
 use std::collections::HashSet;
 
-const MOVE_UNIT: u16 = 32;
+const MOVE_UNIT: f32 = 32.0;
+const MOVE_SPEED: f32 = 256.0;
+// The most a RigidBody player is pushed with, in mass times pixels per second squared.
+const MOVE_FORCE: f32 = 2048.0;
 
+// Players with a Velocity move at MOVE_SPEED while a direction is held; the rest step MOVE_UNIT per press.
+// A RigidBody player is pushed towards that speed by at most MOVE_FORCE instead, so heavier ones are
+// slower to get going and to stop.
+#[derive(Default)]
 pub struct MovePlayer {
-    pub keys_pressed: HashSet<KeyCode>,
+    reader: Option<ReaderId<GameEvent>>,
+    held: HashSet<Action>
 }
 
 impl<'a> System<'a> for MovePlayer {
     type SystemData = (
+        Read<'a, GameEvents>,
+        Read<'a, Gravity>,
+        Read<'a, DeltaTime>,
         ReadStorage<'a, PlayerControl>,
+        WriteStorage<'a, RigidBody>,
+        WriteStorage<'a, Velocity>,
         WriteStorage<'a, Position>,
     );
 
-    fn run(&mut self, (p, mut pos): Self::SystemData) {
-        for (_, pos) in (&p, &mut pos).join() {
-            if self.keys_pressed.contains(&KeyCode::Left) {
-                pos.x = match pos.x {
-                    n if n < MOVE_UNIT => pos.x,
-                    _ => pos.x - MOVE_UNIT,
-                };
-            }
-            
-            if self.keys_pressed.contains(&KeyCode::Right) {
-                pos.x = match pos.x {
-                    n if n > u16::MAX - MOVE_UNIT => pos.x,
-                    _ => pos.x + MOVE_UNIT,
-                };
-            }
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader = Some(world.fetch_mut::<GameEvents>().register_reader());
+    }
+
+    fn run(&mut self, (events, gravity, delta_time, p, mut bodies, mut vel, mut pos): Self::SystemData) {
+        let reader = match self.reader.as_mut() {
+            Some(reader) => reader,
+            None => return
+        };
 
-            if self.keys_pressed.contains(&KeyCode::Up) {
-                pos.y = match pos.y {
-                    n if n < MOVE_UNIT => pos.y,
-                    _ => pos.y - MOVE_UNIT,
-                };
+        let (mut dx, mut dy) = (0.0, 0.0);
+        for event in events.read(reader) {
+            match event {
+                GameEvent::ActionTriggered { action, state: ActionState::Pressed } => {
+                    self.held.insert(*action);
+                    match action {
+                        Action::MoveLeft => dx -= MOVE_UNIT,
+                        Action::MoveRight => dx += MOVE_UNIT,
+                        Action::MoveUp => dy -= MOVE_UNIT,
+                        Action::MoveDown => dy += MOVE_UNIT
+                    }
+                },
+                GameEvent::ActionTriggered { action, state: ActionState::Released } => {
+                    self.held.remove(action);
+                },
+                _ => ()
             }
+        }
+
+        let direction = |negative: Action, positive: Action| {
+            (self.held.contains(&positive) as i32 - self.held.contains(&negative) as i32) as f32
+        };
+        let target = (
+            direction(Action::MoveLeft, Action::MoveRight) * MOVE_SPEED,
+            direction(Action::MoveUp, Action::MoveDown) * MOVE_SPEED
+        );
+
+        for (_, vel, body) in (&p, &mut vel, (&mut bodies).maybe()).join() {
+            // Under gravity, vertical speed is left to the physics.
+            let falling = gravity.0[1] != 0.0 && body.as_ref().map_or(false, |body| body.gravity_scale != 0.0);
 
-            if self.keys_pressed.contains(&KeyCode::Down) {
-                pos.y = match pos.y {
-                    n if n > u16::MAX - MOVE_UNIT => pos.y,
-                    _ => pos.y + MOVE_UNIT,
-                };
+            match body {
+                Some(body) => {
+                    // Enough force to reach the target speed this tick, capped at MOVE_FORCE.
+                    let push = |current: f32, target: f32| {
+                        (body.mass * (target - current) / delta_time.0).max(-MOVE_FORCE).min(MOVE_FORCE)
+                    };
+                    let force = [
+                        push(vel.x, target.0),
+                        if falling { 0.0 } else { push(vel.y, target.1) }
+                    ];
+                    body.apply_force(force);
+                },
+                None => {
+                    vel.x = target.0;
+                    if !falling {
+                        vel.y = target.1;
+                    }
+                }
             }
         }
+
+        for (_, pos, _) in (&p, &mut pos, !&vel).join() {
+            pos.x += dx;
+            pos.y += dy;
+        }
     }
 }
@@ -0,0 +1,45 @@
+use crate::components::default_sound::DefaultSound;
+use crate::audio::AudioPlayer;
+use crate::assets::AssetCache;
+use crate::settings::AudioSettings;
+
+use specs::prelude::*;
+
+use tracing::{debug, error};
+
+// Plays and clears every raised DefaultSound play_flag, and keeps the player's volumes and decoded sounds
+// in step with AudioSettings and the assets loaded for the scene stack.
+pub struct PlayDefaultSounds;
+
+impl<'a> System<'a> for PlayDefaultSounds {
+    type SystemData = (
+        Read<'a, AudioSettings>,
+        Read<'a, AssetCache>,
+        Write<'a, AudioPlayer>,
+        WriteStorage<'a, DefaultSound>,
+    );
+
+    fn run(&mut self, (settings, assets, mut player, mut sounds): Self::SystemData) {
+        player.set_settings(*settings);
+
+        assets.with_loaded(|loaded| {
+            player.retain_sounds(|name| loaded.sound_path(name).is_some());
+
+            for sound in (&mut sounds).join().filter(|sound| sound.play_flag) {
+                sound.play_flag = false;
+
+                let path = match loaded.sound_path(sound.sound_name.as_str()) {
+                    Some(path) => path,
+                    None => {
+                        debug!(sound = %sound.sound_name, "Sound isn't declared by any scene on the stack");
+                        continue;
+                    }
+                };
+
+                if let Err(e) = player.play(sound.sound_name.as_str(), path, sound.channel) {
+                    error!(sound = %sound.sound_name, "Failed to play sound: {:#}", e);
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,56 @@
+use crate::components::{tween::{Tween, TweenProperty}, position::Position, text_display::TextDisplay, mesh_graphic::MeshGraphic};
+use crate::timestep::DeltaTime;
+
+use coffee::graphics::{Color, Rectangle};
+
+use specs::prelude::*;
+
+// Writes each Tween's current values into the components it targets. A step whose component the
+// entity doesn't have is skipped, and finished tweens are left in place holding their last value.
+pub struct PlayTweens;
+
+impl<'a> System<'a> for PlayTweens {
+    type SystemData = (
+        Read<'a, DeltaTime>,
+        WriteStorage<'a, Tween>,
+        WriteStorage<'a, Position>,
+        WriteStorage<'a, TextDisplay>,
+        WriteStorage<'a, MeshGraphic>,
+    );
+
+    fn run(&mut self, (delta_time, mut tweens, mut pos, mut texts, mut meshes): Self::SystemData) {
+        for (tween, mut pos, mut text, mut mesh) in (&mut tweens, (&mut pos).maybe(), (&mut texts).maybe(), (&mut meshes).maybe()).join() {
+            for (property, t) in tween.advance(delta_time.0) {
+                match property {
+                    TweenProperty::Position { from, to } => if let Some(pos) = pos.as_mut() {
+                        pos.x = lerp(from[0], to[0], t);
+                        pos.y = lerp(from[1], to[1], t);
+                    },
+                    TweenProperty::TextColor { from, to } => if let Some(text) = text.as_mut() {
+                        text.color = Color::new(
+                            lerp(from[0], to[0], t),
+                            lerp(from[1], to[1], t),
+                            lerp(from[2], to[2], t),
+                            lerp(from[3], to[3], t)
+                        );
+                    },
+                    TweenProperty::TextSize { from, to } => if let Some(text) = text.as_mut() {
+                        text.size = lerp(from, to, t);
+                    },
+                    TweenProperty::MeshBounds { from, to } => if let Some(mesh) = mesh.as_mut() {
+                        mesh.set_bounds(Rectangle {
+                            x: lerp(from[0], to[0], t),
+                            y: lerp(from[1], to[1], t),
+                            width: lerp(from[2], to[2], t),
+                            height: lerp(from[3], to[3], t)
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
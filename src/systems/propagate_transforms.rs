@@ -0,0 +1,57 @@
+use crate::components::{parent::Parent, local_offset::LocalOffset, position::Position};
+
+use specs::prelude::*;
+
+use std::collections::HashMap;
+
+// Deeper than any hierarchy we build; only reachable through a cycle made in the inspector.
+const MAX_DEPTH: usize = 32;
+
+// Sets each child's Position to its Parent's plus its LocalOffset, parents first so nested children
+// follow in the same tick. Children without a LocalOffset keep their own Position.
+pub struct PropagateTransforms;
+
+impl<'a> System<'a> for PropagateTransforms {
+    type SystemData = (
+        Entities<'a>,
+        ReadStorage<'a, Parent>,
+        ReadStorage<'a, LocalOffset>,
+        WriteStorage<'a, Position>,
+    );
+
+    fn run(&mut self, (entities, parents, offsets, mut pos): Self::SystemData) {
+        let links: HashMap<Entity, (Entity, LocalOffset)> = (&entities, &parents, &offsets).join()
+            .map(|(entity, parent, offset)| (entity, (parent.entity, *offset)))
+            .collect();
+
+        let mut resolved: HashMap<Entity, Position> = HashMap::new();
+        for entity in links.keys() {
+            if let Some(position) = resolve(*entity, &links, &pos, &mut resolved, 0) {
+                // Children that had no Position get one.
+                let _ = pos.insert(*entity, position);
+            }
+        }
+    }
+}
+
+fn resolve(entity: Entity, links: &HashMap<Entity, (Entity, LocalOffset)>, pos: &WriteStorage<Position>,
+           resolved: &mut HashMap<Entity, Position>, depth: usize) -> Option<Position> {
+    if let Some(position) = resolved.get(&entity) {
+        return Some(*position);
+    }
+
+    let position = match links.get(&entity) {
+        Some((parent, offset)) if depth < MAX_DEPTH => {
+            let parent_position = resolve(*parent, links, pos, resolved, depth + 1)?;
+            Position {
+                x: parent_position.x + offset.x,
+                y: parent_position.y + offset.y
+            }
+        },
+        Some(_) => return None,
+        None => *pos.get(entity)?
+    };
+
+    resolved.insert(entity, position);
+    Some(position)
+}
@@ -0,0 +1,37 @@
+use crate::components::{tilemap::Tilemap, position::Position};
+use crate::entities::spawn_queue::{SpawnQueue, SpawnRequest};
+
+use specs::prelude::*;
+
+// Queues each tilemap's object layer once, offset by the tilemap's Position. Done here rather than
+// in the loader because the map's Position isn't inserted until its entity is built.
+pub struct SpawnTilemapObjects;
+
+impl<'a> System<'a> for SpawnTilemapObjects {
+    type SystemData = (
+        Write<'a, SpawnQueue>,
+        WriteStorage<'a, Tilemap>,
+        ReadStorage<'a, Position>,
+    );
+
+    fn run(&mut self, (mut spawn_queue, mut tilemaps, pos): Self::SystemData) {
+        for (tilemap, pos) in (&mut tilemaps, pos.maybe()).join() {
+            if tilemap.objects_spawned {
+                continue;
+            }
+
+            let (x, y) = pos.map(|p| (p.x, p.y)).unwrap_or((0.0, 0.0));
+            for object in &tilemap.objects {
+                let request = object.overrides.iter().cloned().fold(
+                    SpawnRequest::new(object.prefab_id.as_str())
+                        .with_position(x + object.position[0], y + object.position[1]),
+                    SpawnRequest::with_override
+                );
+
+                spawn_queue.spawn(request);
+            }
+
+            tilemap.objects_spawned = true;
+        }
+    }
+}
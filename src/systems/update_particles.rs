@@ -0,0 +1,24 @@
+use crate::components::{particle_emitter::ParticleEmitter, position::Position};
+use crate::random::GameRng;
+use crate::timestep::DeltaTime;
+
+use specs::prelude::*;
+
+// Runs on the fixed tick rather than in draw, so particle logic also runs headless.
+pub struct UpdateParticles;
+
+impl<'a> System<'a> for UpdateParticles {
+    type SystemData = (
+        Read<'a, DeltaTime>,
+        Write<'a, GameRng>,
+        ReadStorage<'a, Position>,
+        WriteStorage<'a, ParticleEmitter>,
+    );
+
+    fn run(&mut self, (delta_time, mut rng, pos, mut emitters): Self::SystemData) {
+        for (emitter, pos) in (&mut emitters, pos.maybe()).join() {
+            let origin = pos.map(|p| [p.x, p.y]).unwrap_or([0.0, 0.0]);
+            emitter.update(origin, delta_time.0, rng.rng());
+        }
+    }
+}
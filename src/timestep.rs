@@ -0,0 +1,118 @@
+use std::time::{Duration, Instant};
+
+use specs::prelude::*;
+
+use crate::components::position::Position;
+use crate::components::previous_position::PreviousPosition;
+use crate::rendering::camera::Camera;
+
+pub const DEFAULT_TICK_RATE: u32 = 60;
+
+// Caps catch-up after a long stall (e.g. dragging the window) so updates can't snowball.
+const MAX_TICKS_PER_UPDATE: u32 = 8;
+
+// Seconds simulated by each fixed tick.
+#[derive(Debug, Copy, Clone)]
+pub struct DeltaTime(pub f32);
+
+impl Default for DeltaTime {
+    fn default() -> Self {
+        DeltaTime(1.0 / DEFAULT_TICK_RATE as f32)
+    }
+}
+
+// How far between the last tick and the next one the current draw falls, in [0, 1).
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Interpolation(pub f32);
+
+#[derive(Debug, Clone)]
+pub struct FixedTimestep {
+    tick_rate: u32,
+    accumulator: Duration,
+    last_instant: Option<Instant>,
+    total_ticks: u64
+}
+
+impl FixedTimestep {
+    pub fn new(tick_rate: u32) -> Self {
+        FixedTimestep {
+            tick_rate: tick_rate.max(1),
+            accumulator: Duration::from_secs(0),
+            last_instant: None,
+            total_ticks: 0
+        }
+    }
+
+    pub fn tick_rate(&self) -> u32 {
+        self.tick_rate
+    }
+
+    pub fn tick_duration(&self) -> Duration {
+        Duration::from_secs(1) / self.tick_rate
+    }
+
+    pub fn delta_time(&self) -> DeltaTime {
+        DeltaTime(self.tick_duration().as_secs_f32())
+    }
+
+    pub fn total_ticks(&self) -> u64 {
+        self.total_ticks
+    }
+
+    // Measures wall-clock time since the previous call and returns how many ticks to run now.
+    pub fn advance(&mut self) -> u32 {
+        let now = Instant::now();
+        let elapsed = match self.last_instant {
+            Some(last_instant) => now - last_instant,
+            None => self.tick_duration()
+        };
+        self.last_instant = Some(now);
+
+        self.advance_by(elapsed)
+    }
+
+    // Deterministic variant for headless runs, where time is whatever the caller says it is.
+    pub fn advance_by(&mut self, elapsed: Duration) -> u32 {
+        let tick_duration = self.tick_duration();
+        self.accumulator += elapsed;
+
+        let mut ticks = 0;
+        while self.accumulator >= tick_duration {
+            self.accumulator -= tick_duration;
+            ticks += 1;
+
+            if ticks == MAX_TICKS_PER_UPDATE {
+                self.accumulator = Duration::from_secs(0);
+                break;
+            }
+        }
+
+        self.total_ticks += ticks as u64;
+        ticks
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        Interpolation(self.accumulator.as_secs_f32() / self.tick_duration().as_secs_f32())
+    }
+}
+
+// Run by the scene before each tick, so draws can blend from these towards the tick's result.
+pub struct RecordPreviousPositions;
+
+impl<'a> System<'a> for RecordPreviousPositions {
+    type SystemData = (
+        Entities<'a>,
+        Write<'a, Camera>,
+        ReadStorage<'a, Position>,
+        WriteStorage<'a, PreviousPosition>,
+    );
+
+    fn run(&mut self, (entities, mut camera, pos, mut previous): Self::SystemData) {
+        camera.previous_position = Some(camera.position);
+
+        for (entity, pos) in (&entities, &pos).join() {
+            // Only fails for dead entities, which join doesn't return.
+            let _ = previous.insert(entity, PreviousPosition { x: pos.x, y: pos.y });
+        }
+    }
+}